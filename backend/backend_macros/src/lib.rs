@@ -3,8 +3,8 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::{ToTokens, quote};
 use syn::{
-    Attribute, Data, DeriveInput, Fields, GenericArgument, PathArguments, Type, parse_macro_input,
-    parse_quote,
+    Attribute, Data, DeriveInput, Fields, GenericArgument, Path, PathArguments, Type,
+    parse_macro_input, parse_quote,
 };
 
 #[proc_macro_attribute]
@@ -73,13 +73,31 @@ fn generate_conversion_function(type_input: &Type) -> proc_macro2::TokenStream {
                     Box::new(|content: &str| serde_json::Value::Number(content.parse::<i32>().unwrap().into()))
                 }
             }
+            "i64" => {
+                quote! {
+                    Box::new(|content: &str| serde_json::Value::Number(content.parse::<i64>().unwrap().into()))
+                }
+            }
+            // Dates are deserialized from strings in ISO 8601 format
+            "NaiveDate" => {
+                quote! {
+                    Box::new(|content: &str| serde_json::Value::String(content.to_owned()))
+                }
+            }
+            // Timestamps are printed with a space instead of "T" separating date and time
+            "NaiveDateTime" => {
+                quote! {
+                    Box::new(|content: &str| serde_json::Value::String(content.replacen(' ', "T", 1)))
+                }
+            }
             "bool" => {
                 quote! {
                     Box::new(|content: &str| {
                         serde_json::Value::Bool(
+                            // PostgreSQL prints booleans as t and f
                             match content.to_lowercase().as_str() {
-                                "true" | "1" => true,
-                                "false" | "0" => false,
+                                "true" | "t" | "1" => true,
+                                "false" | "f" | "0" => false,
                                 _ => panic!("Could not parse '{}' to bool", content),
                             })
                     })
@@ -94,17 +112,38 @@ fn generate_conversion_function(type_input: &Type) -> proc_macro2::TokenStream {
                 let GenericArgument::Type(inner_type) = type_args.first().unwrap() else {
                     panic!("Unsupported type of path args");
                 };
-                let inner_conversion = generate_conversion_function(inner_type);
-                quote! {
-                    // FIXME Do None values always arrive as "NULL"?
-                    Box::new(|content: &str| {
-                        if content == "NULL" {
-                            return serde_json::Value::Null;
-                        }
+                // NULL values are handled by the generator of the field
+                generate_conversion_function(inner_type)
+            }
+            _ => panic!("Unsupported type name \"{}\"", type_name),
+        },
+        None => panic!(
+            "Could not determine type name of type path \"{}\"",
+            type_input.to_token_stream()
+        ),
+    }
+}
 
-                        (#inner_conversion)(content)
-                    })
-                }
+fn generate_field_type_info(type_input: &Type, nullable: bool) -> proc_macro2::TokenStream {
+    let type_description = get_type_description(type_input);
+    let field_type = match type_description {
+        Some((type_name, type_params)) => match type_name.as_str() {
+            "String" => quote! { crate::json_field_conversion::FieldType::Text },
+            "i32" | "i64" => quote! { crate::json_field_conversion::FieldType::Integer },
+            "bool" => quote! { crate::json_field_conversion::FieldType::Boolean },
+            "f32" | "f64" => quote! { crate::json_field_conversion::FieldType::Double },
+            "NaiveDate" => quote! { crate::json_field_conversion::FieldType::Date },
+            "NaiveDateTime" => quote! { crate::json_field_conversion::FieldType::DateTime },
+            "Option" => {
+                let PathArguments::AngleBracketed(angle_bracket_args) = type_params else {
+                    panic!("Unsupported kind of path args");
+                };
+                let type_args = angle_bracket_args.args;
+                assert_eq!(type_args.len(), 1);
+                let GenericArgument::Type(inner_type) = type_args.first().unwrap() else {
+                    panic!("Unsupported type of path args");
+                };
+                return generate_field_type_info(inner_type, true);
             }
             _ => panic!("Unsupported type name \"{}\"", type_name),
         },
@@ -112,9 +151,32 @@ fn generate_conversion_function(type_input: &Type) -> proc_macro2::TokenStream {
             "Could not determine type name of type path \"{}\"",
             type_input.to_token_stream()
         ),
+    };
+
+    quote! {
+        crate::json_field_conversion::FieldTypeInfo {
+            field_type: #field_type,
+            nullable: #nullable,
+        }
     }
 }
 
+// Extract the table of "#[diesel(table_name = ...)]" if there is any
+fn get_diesel_table_name(attrs: &[Attribute]) -> Option<Path> {
+    let mut table_name = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("diesel")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table_name") {
+                table_name = Some(meta.value()?.parse::<Path>()?);
+            }
+            Ok(())
+        });
+    }
+
+    table_name
+}
+
 #[proc_macro_derive(JsonFieldConversionGenerator)]
 pub fn implement_json_field_conversion(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
@@ -137,20 +199,77 @@ pub fn implement_json_field_conversion(item: TokenStream) -> TokenStream {
         let field_case = field_name.as_ref().unwrap().to_string();
         let field_type = &f.ty;
         let conversion = generate_conversion_function(field_type);
+        // Deserializing NULL into fields which are not nullable fails with a descriptive error
+        quote! {
+            #field_case => {
+                let conversion: Box<dyn Fn(&str) -> serde_json::Value> = #conversion;
+                Box::new(move |opt_content: Option<&str>| match opt_content {
+                    Some(content) => conversion(content),
+                    None => serde_json::Value::Null,
+                })
+            }
+        }
+    });
+
+    let field_type_cases = struct_fields.iter().map(|f| {
+        let field_name = &f.ident;
+        let field_case = field_name.as_ref().unwrap().to_string();
+        let field_type_info = generate_field_type_info(&f.ty, false);
         quote! {
-            #field_case => #conversion,
+            #field_case => Some(#field_type_info),
+        }
+    });
+
+    let field_names = struct_fields.iter().map(|f| {
+        let field_name = &f.ident;
+        field_name.as_ref().unwrap().to_string()
+    });
+
+    let table_name = get_diesel_table_name(&input.attrs);
+    let column_name_cases = struct_fields.iter().map(|f| {
+        let field_name = &f.ident;
+        let field_case = field_name.as_ref().unwrap().to_string();
+        // Diesel knows the actual column name of fields whose column name contains non-ASCII characters
+        match &table_name {
+            Some(table_name) => quote! {
+                #field_case => Some(<crate::schema::#table_name::#field_name as diesel::Column>::NAME),
+            },
+            None => quote! {
+                #field_case => Some(#field_case),
+            },
         }
     });
 
     let expanded = quote! {
         impl JsonFieldConversion for #struct_name {
             #[frb(ignore)]
-            fn get_json_value_generator(field_name: &str )-> Box<dyn Fn(&str) -> serde_json::Value> {
+            fn get_json_value_generator(field_name: &str )-> Box<dyn Fn(Option<&str>) -> serde_json::Value> {
                 match field_name {
                 #(#field_conversion_cases)*
                 _ => panic!("Field {} is unknown", field_name),
                 }
             }
+
+            #[frb(ignore)]
+            fn get_field_type(field_name: &str) -> Option<crate::json_field_conversion::FieldTypeInfo> {
+                match field_name {
+                #(#field_type_cases)*
+                _ => None,
+                }
+            }
+
+            #[frb(ignore)]
+            fn get_field_names() -> Vec<&'static str> {
+                vec![#(#field_names),*]
+            }
+
+            #[frb(ignore)]
+            fn get_column_name(field_name: &str) -> Option<&'static str> {
+                match field_name {
+                #(#column_name_cases)*
+                _ => None,
+                }
+            }
         }
     };
 
@@ -27,11 +27,11 @@ fn get_current_thread_name() -> String {
     return fallback_thread_name.to_owned();
 }
 
+// Severe messages logged by each thread
+type MessageEntries = Arc<RwLock<HashMap<String, Arc<RwLock<Vec<String>>>>>>;
+
 fn get_message_entry_lock() -> Arc<RwLock<Vec<String>>> {
-    static LOGGER: LazyLock<(
-        LoggerHandle,
-        Arc<RwLock<HashMap<String, Arc<RwLock<Vec<String>>>>>>,
-    )> = LazyLock::new(|| {
+    static LOGGER: LazyLock<(LoggerHandle, MessageEntries)> = LazyLock::new(|| {
         let default_test_name = String::from("no_nextest_test_name");
         let test_case_name = std::env::var("NEXTEST_TEST_NAME")
             .inspect_err(|err| {
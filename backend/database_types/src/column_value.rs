@@ -0,0 +1,13 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValue {
+    Text(String),
+    Integer(i64),
+    Boolean(bool),
+    Double(f64),
+    Date(NaiveDate),
+    // Timestamps with time zone are converted to UTC
+    DateTime(NaiveDateTime),
+    Null,
+}
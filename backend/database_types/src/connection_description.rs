@@ -1,28 +1,220 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DatabaseBackend {
     #[cfg(feature = "mysql")]
     MySql,
     #[cfg(feature = "postgres")]
     PostgreSql,
+    // Database in a local file. It needs neither a server nor credentials.
+    #[cfg(feature = "sqlite")]
+    Sqlite,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SshAuthentication {
+    Password(String),
+    // Only the path is stored. The key itself is read when connecting.
+    PrivateKey {
+        path: String,
+        passphrase: Option<String>,
+    },
+    // Use the keys of the agent referenced by SSH_AUTH_SOCK
+    Agent,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SshTunnelMode {
+    // Forward a local port to the database and connect to it as usual
+    #[default]
+    PortForwarding,
+    // Fallback running the CLI clients of the databases on the SSH server
+    RemoteCli,
+}
+
+// Single SSH server of a tunnel
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SshHop {
+    pub username: String,
+    pub authentication: SshAuthentication,
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "StoredSshTunnelDescription")]
 pub struct SshTunnelDescription {
     pub username: String,
-    pub password: String,
+    pub authentication: SshAuthentication,
     pub host: String,
     pub port: u16,
+    // Servers (e.g. bastion hosts) passed in the given order before reaching the host of the tunnel
+    #[serde(default)]
+    pub jump_hosts: Vec<SshHop>,
+    #[serde(default)]
+    pub mode: SshTunnelMode,
+}
+
+// Profiles stored before other authentication methods were supported contain the password of the tunnel instead
+#[derive(Deserialize)]
+struct StoredSshTunnelDescription {
+    username: String,
+    authentication: Option<SshAuthentication>,
+    password: Option<String>,
+    host: String,
+    port: u16,
+    #[serde(default)]
+    jump_hosts: Vec<SshHop>,
+    #[serde(default)]
+    mode: SshTunnelMode,
+}
+
+impl TryFrom<StoredSshTunnelDescription> for SshTunnelDescription {
+    type Error = String;
+
+    fn try_from(stored_tunnel: StoredSshTunnelDescription) -> Result<Self, Self::Error> {
+        let authentication = match (stored_tunnel.authentication, stored_tunnel.password) {
+            (Some(authentication), None) => authentication,
+            (None, Some(password)) => SshAuthentication::Password(password),
+            (Some(_), Some(_)) => {
+                return Err(String::from(
+                    "SSH tunnel must not contain both 'authentication' and 'password'",
+                ));
+            }
+            (None, None) => return Err(String::from("missing field `authentication`")),
+        };
+
+        Ok(Self {
+            username: stored_tunnel.username,
+            authentication,
+            host: stored_tunnel.host,
+            port: stored_tunnel.port,
+            jump_hosts: stored_tunnel.jump_hosts,
+            mode: stored_tunnel.mode,
+        })
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl SshTunnelDescription {
+    // All servers of the tunnel in the order they are connected to
+    pub fn get_hops(&self) -> Vec<SshHop> {
+        let mut hops = self.jump_hosts.clone();
+        hops.push(SshHop {
+            username: self.username.clone(),
+            authentication: self.authentication.clone(),
+            host: self.host.clone(),
+            port: self.port,
+        });
+        hops
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TlsMode {
+    Disabled,
+    // Use TLS if the server supports it
+    #[default]
+    Preferred,
+    Required,
+    // Verify the certificate of the server against the CA certificate
+    VerifyCa,
+    // Additionally verify that the certificate of the server matches its host name
+    VerifyFull,
+}
+
+// Paths of certificates are stored only. The certificates themselves are read when connecting.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TlsOptions {
+    pub mode: TlsMode,
+    pub ca_certificate_path: Option<String>,
+    pub client_certificate_path: Option<String>,
+    pub client_key_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TimeoutOptions {
+    // Applies to establishing TCP connections as well as the handshakes of SSH and the database
+    pub connect_seconds: u32,
+    pub authentication_seconds: u32,
+    // Statements running longer are cancelled
+    pub query_seconds: u32,
+}
+
+impl Default for TimeoutOptions {
+    fn default() -> Self {
+        Self {
+            connect_seconds: 10,
+            authentication_seconds: 30,
+            query_seconds: 60,
+        }
+    }
+}
+
+impl TimeoutOptions {
+    pub fn get_connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_seconds.into())
+    }
+
+    pub fn get_authentication_timeout(&self) -> Duration {
+        Duration::from_secs(self.authentication_seconds.into())
+    }
+
+    pub fn get_query_timeout(&self) -> Duration {
+        Duration::from_secs(self.query_seconds.into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ConnectionDescription {
     pub backend: DatabaseBackend,
     pub host: String,
     pub port: u16,
     pub user: String,
     pub password: String,
+    // Path of the database file for SQLite
     pub name: String,
     pub ssh_tunnel: Option<SshTunnelDescription>,
+    #[serde(default)]
+    pub tls: TlsOptions,
+    // Connect via the Unix socket instead of host and port (only for connections without SSH tunnel)
+    #[serde(default)]
+    pub socket_path: Option<String>,
+    // Command logging in to the database on the SSH server if the CLI is used. None selects the default of the backend.
+    #[serde(default)]
+    pub cli_command_template: Option<String>,
+    #[serde(default)]
+    pub timeouts: TimeoutOptions,
+}
+
+impl ConnectionDescription {
+    // Host, port and credentials are meaningless for SQLite and left empty
+    #[cfg(feature = "sqlite")]
+    pub fn for_sqlite_file(path: String) -> Self {
+        Self {
+            backend: DatabaseBackend::Sqlite,
+            host: String::new(),
+            port: 0,
+            user: String::new(),
+            password: String::new(),
+            name: path,
+            ssh_tunnel: None,
+            tls: TlsOptions::default(),
+            socket_path: None,
+            cli_command_template: None,
+            timeouts: TimeoutOptions::default(),
+        }
+    }
+
+    // Whether the database is reached via network or socket instead of a local file
+    pub fn has_server(&self) -> bool {
+        match self.backend {
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => true,
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => true,
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => false,
+        }
+    }
 }
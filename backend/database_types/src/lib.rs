@@ -1 +1,2 @@
-pub mod connection_description;
\ No newline at end of file
+pub mod column_value;
+pub mod connection_description;
@@ -5,7 +5,13 @@ pub mod profile;
 
 pub use database_types::connection_description::ConnectionDescription;
 pub use database_types::connection_description::DatabaseBackend;
+pub use database_types::connection_description::SshAuthentication;
+pub use database_types::connection_description::SshHop;
 pub use database_types::connection_description::SshTunnelDescription;
+pub use database_types::connection_description::SshTunnelMode;
+pub use database_types::connection_description::TimeoutOptions;
+pub use database_types::connection_description::TlsMode;
+pub use database_types::connection_description::TlsOptions;
 use flutter_rust_bridge::frb;
 pub use sepa_types::creditor::Creditor;
 pub use sepa_types::creditor_id::CreditorID;
@@ -19,14 +25,66 @@ pub enum _DatabaseBackend {
     MySql,
     #[cfg(feature = "postgres")]
     PostgreSql,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+#[frb(mirror(SshAuthentication))]
+pub enum _SshAuthentication {
+    Password(String),
+    PrivateKey {
+        path: String,
+        passphrase: Option<String>,
+    },
+    Agent,
+}
+
+#[frb(mirror(SshTunnelMode))]
+pub enum _SshTunnelMode {
+    PortForwarding,
+    RemoteCli,
+}
+
+#[frb(mirror(SshHop))]
+pub struct _SshHop {
+    pub username: String,
+    pub authentication: SshAuthentication,
+    pub host: String,
+    pub port: u16,
 }
 
 #[frb(mirror(SshTunnelDescription))]
 pub struct _SshTunnelDescription {
     pub username: String,
-    pub password: String,
+    pub authentication: SshAuthentication,
     pub host: String,
     pub port: u16,
+    pub jump_hosts: Vec<SshHop>,
+    pub mode: SshTunnelMode,
+}
+
+#[frb(mirror(TlsMode))]
+pub enum _TlsMode {
+    Disabled,
+    Preferred,
+    Required,
+    VerifyCa,
+    VerifyFull,
+}
+
+#[frb(mirror(TlsOptions))]
+pub struct _TlsOptions {
+    pub mode: TlsMode,
+    pub ca_certificate_path: Option<String>,
+    pub client_certificate_path: Option<String>,
+    pub client_key_path: Option<String>,
+}
+
+#[frb(mirror(TimeoutOptions))]
+pub struct _TimeoutOptions {
+    pub connect_seconds: u32,
+    pub authentication_seconds: u32,
+    pub query_seconds: u32,
 }
 
 #[frb(mirror(ConnectionDescription))]
@@ -38,6 +96,10 @@ pub struct _ConnectionDescription {
     pub password: String,
     pub name: String,
     pub ssh_tunnel: Option<SshTunnelDescription>,
+    pub tls: TlsOptions,
+    pub socket_path: Option<String>,
+    pub cli_command_template: Option<String>,
+    pub timeouts: TimeoutOptions,
 }
 
 #[frb(mirror(Creditor))]
@@ -70,3 +70,60 @@ pub fn save_profile(profile: Profile) {
         );
     }
 }
+
+// The profiles refer to PostgreSQL databases
+#[cfg(all(test, feature = "postgres"))]
+mod test {
+    use database_types::connection_description::SshAuthentication;
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    // Profile as stored before other SSH authentication methods than passwords were supported
+    const LEGACY_PROFILE: &str = r#"
+[connection]
+backend = "PostgreSql"
+host = "db.example.org"
+port = 5432
+user = "member"
+password = "database password"
+name = "members"
+
+[connection.ssh_tunnel]
+username = "tunnel"
+password = "tunnel password"
+host = "ssh.example.org"
+port = 22
+"#;
+
+    #[test]
+    fn test_load_legacy_profile() {
+        let profile: Profile = toml::from_str(LEGACY_PROFILE).unwrap();
+        let connection = profile.connection.unwrap();
+        let ssh_tunnel = connection.ssh_tunnel.clone().unwrap();
+
+        assert_that!(connection.password).is_equal_to(String::from("database password"));
+        assert_that!(ssh_tunnel.authentication)
+            .is_equal_to(SshAuthentication::Password(String::from("tunnel password")));
+
+        let serialized_profile = toml::to_string_pretty(&Profile {
+            creditor: None,
+            connection: Some(connection.clone()),
+        })
+        .unwrap();
+        let reloaded_profile: Profile = toml::from_str(&serialized_profile).unwrap();
+
+        assert_that!(reloaded_profile.connection).is_equal_to(Some(connection));
+    }
+
+    #[test]
+    fn test_reject_ambiguous_tunnel_authentication() {
+        let ambiguous_profile =
+            LEGACY_PROFILE.replace("port = 22", "port = 22\nauthentication = \"Agent\"");
+        let result = toml::from_str::<Profile>(&ambiguous_profile);
+
+        assert_that!(result.is_err()).is_true();
+        assert_that!(result.err().unwrap().to_string())
+            .contains("both 'authentication' and 'password'");
+    }
+}
@@ -0,0 +1,3 @@
+DROP TABLE member;
+
+DROP TABLE schema_version;
@@ -0,0 +1,3 @@
+DROP TABLE member_exit;
+
+DELETE FROM schema_version WHERE version = 2;
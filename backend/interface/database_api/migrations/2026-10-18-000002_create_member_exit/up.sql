@@ -0,0 +1,9 @@
+-- Members who exited the club are kept until they are explicitly purged
+CREATE TABLE IF NOT EXISTS member_exit (
+    membership_id integer NOT NULL PRIMARY KEY,
+    exit_date date NOT NULL,
+    exit_reason varchar(255) DEFAULT NULL,
+    FOREIGN KEY (membership_id) REFERENCES member (membership_id)
+);
+
+INSERT INTO schema_version (version, description) VALUES (2, 'Create member exit table');
@@ -0,0 +1,3 @@
+DROP TABLE member_change;
+
+DELETE FROM schema_version WHERE version = 3;
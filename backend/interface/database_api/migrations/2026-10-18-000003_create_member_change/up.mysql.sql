@@ -0,0 +1,13 @@
+-- Audit trail of all changes applied to members. Values are stored in their textual representation.
+CREATE TABLE IF NOT EXISTS member_change (
+    change_id integer NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    changed_at timestamp NOT NULL,
+    author varchar(255) NOT NULL,
+    membership_id integer NOT NULL,
+    column_name varchar(255) NOT NULL,
+    previous_value varchar(255) DEFAULT NULL,
+    new_value varchar(255) DEFAULT NULL,
+    FOREIGN KEY (membership_id) REFERENCES member (membership_id)
+);
+
+INSERT INTO schema_version (version, description) VALUES (3, 'Create member change table');
@@ -0,0 +1,249 @@
+use database_types::connection_description::{ConnectionDescription, DatabaseBackend};
+use flutter_rust_bridge::frb;
+use log::error;
+
+/* Templates describe the command which logs in to the database on the SSH server. Placeholders like "{host}" are
+ * replaced by shell quoted values. Literal braces (e.g. of shell variables) have to be doubled like "{{" and "}}".
+ */
+const PLACEHOLDERS: [&str; 5] = ["host", "port", "user", "database", "output_options"];
+
+// The output of the CLI is only parseable with these options
+const REQUIRED_PLACEHOLDER: &str = "output_options";
+
+fn get_output_options(backend: &DatabaseBackend) -> String {
+    return match backend {
+        // NOTE -q suppresses command tags like "COPY 2" following the output of queries
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => String::from("--csv -q -X"),
+        // NOTE --force keeps the session alive after failing statements and --unbuffered flushes the output of each one
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => String::from("--batch --force --unbuffered"),
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => String::new(),
+    };
+}
+
+// Quote the value for POSIX shells so it is passed as a single argument without any expansion
+pub(crate) fn quote_for_shell(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// Return the text between the braces of all placeholders
+fn parse_placeholders(template: &str) -> Option<Vec<String>> {
+    let mut placeholders = vec![];
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '{' => {
+                let mut placeholder = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(placeholder_char) => placeholder.push(placeholder_char),
+                        None => {
+                            error!(
+                                "Command template '{}' has an unclosed placeholder",
+                                template
+                            );
+                            return None;
+                        }
+                    }
+                }
+
+                placeholders.push(placeholder);
+            }
+            '}' => {
+                error!("Command template '{}' has an unmatched '}}'", template);
+                return None;
+            }
+            _ => {}
+        }
+    }
+
+    Some(placeholders)
+}
+
+pub(crate) fn validate_command_template(template: &str) -> bool {
+    let opt_placeholders = parse_placeholders(template);
+
+    if opt_placeholders.is_none() {
+        return false;
+    }
+
+    let placeholders = opt_placeholders.unwrap();
+    let opt_unknown_placeholder = placeholders
+        .iter()
+        .find(|placeholder| !PLACEHOLDERS.contains(&placeholder.as_str()));
+
+    if let Some(unknown_placeholder) = opt_unknown_placeholder {
+        error!(
+            "Command template '{}' has the unknown placeholder '{}'",
+            template, unknown_placeholder
+        );
+        return false;
+    }
+
+    if !placeholders
+        .iter()
+        .any(|placeholder| placeholder == REQUIRED_PLACEHOLDER)
+    {
+        error!(
+            "Command template '{}' lacks the placeholder '{{{}}}'",
+            template, REQUIRED_PLACEHOLDER
+        );
+        return false;
+    }
+
+    true
+}
+
+// Replace all placeholders by the given values which have to be quoted already
+fn substitute_placeholders(template: &str, values: &[(&str, String)]) -> String {
+    let mut command = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                command.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                command.push('}');
+            }
+            '{' => {
+                let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let opt_value = values
+                    .iter()
+                    .find(|(name, _)| *name == placeholder)
+                    .map(|(_, value)| value.as_str());
+                command.push_str(opt_value.unwrap_or_default());
+            }
+            _ => command.push(c),
+        }
+    }
+
+    command
+}
+
+pub(crate) fn render_command_template(connection: &ConnectionDescription) -> Option<String> {
+    let template = connection
+        .cli_command_template
+        .clone()
+        .unwrap_or_else(|| get_default_command_template(connection.backend.clone()));
+
+    if !validate_command_template(&template) {
+        return None;
+    }
+
+    Some(substitute_placeholders(
+        &template,
+        &[
+            ("host", quote_for_shell(&connection.host)),
+            ("port", connection.port.to_string()),
+            ("user", quote_for_shell(&connection.user)),
+            ("database", quote_for_shell(&connection.name)),
+            ("output_options", get_output_options(&connection.backend)),
+        ],
+    ))
+}
+
+// Template used for connections which do not specify one
+#[frb(sync)]
+pub fn get_default_command_template(backend: DatabaseBackend) -> String {
+    return match backend {
+        // NOTE stderr contains "Password for user XY:" resulting in a warning making the test fail
+        // NOTE Output has to be flushed line by line since the session is read until the sentinel of each statement
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => String::from(
+            "stdbuf -oL psql {output_options} -d {database} -h {host} -p {port} -U {user} -W 2>/dev/null",
+        ),
+        // NOTE stderr contains "Enter password:" resulting in a warning making the test fail
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => String::from(
+            "mysql --default-character-set=utf8mb4 {output_options} {database} -h {host} -P {port} -u {user} -p \
+             2>/dev/null",
+        ),
+        // Database files are opened locally only. The empty template is invalid.
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => String::new(),
+    };
+}
+
+#[frb(sync)]
+pub fn is_valid_command_template(template: String) -> bool {
+    validate_command_template(&template)
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_quote_for_shell() {
+        assert_that!(quote_for_shell("members")).is_equal_to(String::from("'members'"));
+        assert_that!(quote_for_shell("it's; rm -rf $HOME"))
+            .is_equal_to(String::from("'it'\\''s; rm -rf $HOME'"));
+    }
+
+    #[test]
+    fn test_validate_command_template() {
+        assert_that!(validate_command_template(
+            "sudo -u postgres psql {output_options} -d {database}"
+        ))
+        .is_true();
+        assert_that!(validate_command_template(
+            "mariadb {output_options} -e \"${{HOME}}\" {database}"
+        ))
+        .is_true();
+
+        // Output options are required for parsing the output
+        assert_that!(validate_command_template("psql -d {database}")).is_false();
+        assert_that!(validate_command_template(
+            "psql {output_options} {password}"
+        ))
+        .is_false();
+        assert_that!(validate_command_template("psql {output_options} {database")).is_false();
+        assert_that!(validate_command_template("psql {output_options} database}")).is_false();
+    }
+
+    #[test]
+    fn test_substitute_placeholders() {
+        let command = substitute_placeholders(
+            "sudo -u postgres psql {output_options} -d {database} -c \"${{X}}\"",
+            &[
+                ("output_options", String::from("--csv")),
+                ("database", quote_for_shell("club's members")),
+            ],
+        );
+
+        assert_that!(command).is_equal_to(String::from(
+            "sudo -u postgres psql --csv -d 'club'\\''s members' -c \"${X}\"",
+        ));
+    }
+
+    #[test]
+    fn test_default_command_templates() {
+        #[cfg(feature = "postgres")]
+        assert_that!(validate_command_template(&get_default_command_template(
+            DatabaseBackend::PostgreSql
+        )))
+        .is_true();
+        #[cfg(feature = "mysql")]
+        assert_that!(validate_command_template(&get_default_command_template(
+            DatabaseBackend::MySql
+        )))
+        .is_true();
+    }
+}
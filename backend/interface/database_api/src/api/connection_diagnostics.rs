@@ -0,0 +1,975 @@
+use crate::api::command_template::{quote_for_shell, render_command_template};
+use crate::api::connection_manager::RUNTIME;
+use crate::api::host_key::{HostKeyStatus, get_fingerprint};
+use crate::connection::{
+    SshClient, authenticate_ssh_client, connect_ssh_client, try_establish_orm_connection,
+};
+use crate::db_connection::DbConnection;
+use crate::migration::get_latest_version;
+use crate::orm_connection::OrmConnection;
+use crate::port_forwarding::start_port_forwarding;
+use crate::ssh_connection::SshConnection;
+use database_types::connection_description::{
+    ConnectionDescription, DatabaseBackend, SshAuthentication, SshHop, SshTunnelMode,
+    TimeoutOptions,
+};
+use log::{info, warn};
+use russh::ChannelMsg;
+use russh::client;
+use sea_query::{Expr, ExprTrait, Query};
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::net::{TcpStream, lookup_host};
+use tokio::time::timeout;
+
+// Programs of the command template whose availability is checked on the SSH server
+const CLI_PROGRAMS: [&str; 3] = ["psql", "mysql", "mariadb"];
+
+// Stages are declared in the order they are run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticStage {
+    NameResolution,
+    TcpConnection,
+    SshHandshake,
+    SshAuthentication,
+    RemoteCli,
+    DatabaseLogin,
+    SchemaVersion,
+    MemberTable,
+}
+
+const STAGES: [DiagnosticStage; 8] = [
+    DiagnosticStage::NameResolution,
+    DiagnosticStage::TcpConnection,
+    DiagnosticStage::SshHandshake,
+    DiagnosticStage::SshAuthentication,
+    DiagnosticStage::RemoteCli,
+    DiagnosticStage::DatabaseLogin,
+    DiagnosticStage::SchemaVersion,
+    DiagnosticStage::MemberTable,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StageOutcome {
+    Passed { details: String },
+    // The stage passed but the database requires attention
+    Warning { details: String },
+    Failed { reason: String },
+    Skipped { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageReport {
+    pub stage: DiagnosticStage,
+    // Host, SSH hop, database or table the stage checked
+    pub target: String,
+    pub outcome: StageOutcome,
+    pub duration_ms: u32,
+}
+
+// Stages of tunnels with jump hosts are reported once per hop
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionReport {
+    pub stages: Vec<StageReport>,
+}
+
+impl ConnectionReport {
+    fn record_stage(
+        &mut self,
+        stage: DiagnosticStage,
+        target: String,
+        outcome: StageOutcome,
+        start: Instant,
+    ) {
+        match &outcome {
+            StageOutcome::Failed { reason } => {
+                warn!("Stage {:?} of '{}' failed: {}", stage, target, reason)
+            }
+            _ => info!("Stage {:?} of '{}': {:?}", stage, target, outcome),
+        }
+
+        self.stages.push(StageReport {
+            stage,
+            target,
+            outcome,
+            duration_ms: u32::try_from(start.elapsed().as_millis()).unwrap_or(u32::MAX),
+        });
+    }
+
+    // Run the action as the given stage and return its value if it passed
+    async fn run_stage<ValueType>(
+        &mut self,
+        stage: DiagnosticStage,
+        target: String,
+        action: impl AsyncFnOnce() -> Result<(ValueType, String), String>,
+    ) -> Option<ValueType> {
+        let start = Instant::now();
+
+        return match action().await {
+            Ok((value, details)) => {
+                self.record_stage(stage, target, StageOutcome::Passed { details }, start);
+                Some(value)
+            }
+            Err(reason) => {
+                self.record_stage(stage, target, StageOutcome::Failed { reason }, start);
+                None
+            }
+        };
+    }
+
+    // Insert the stages which did not run at their position
+    fn add_skipped_stages(&mut self, connection: &ConnectionDescription) {
+        let opt_failed_stage = self
+            .stages
+            .iter()
+            .find(|report| matches!(report.outcome, StageOutcome::Failed { .. }))
+            .map(|report| report.stage);
+
+        for stage in STAGES {
+            if self.stages.iter().any(|report| report.stage == stage) {
+                continue;
+            }
+
+            let reason = match opt_failed_stage {
+                Some(failed_stage) if is_stage_applicable(stage, connection) => {
+                    format!("Requires the failed stage {:?}", failed_stage)
+                }
+                _ => String::from("Not applicable to the settings of the connection"),
+            };
+
+            let position = self
+                .stages
+                .iter()
+                .position(|report| report.stage > stage)
+                .unwrap_or(self.stages.len());
+
+            self.stages.insert(
+                position,
+                StageReport {
+                    stage,
+                    target: String::new(),
+                    outcome: StageOutcome::Skipped { reason },
+                    duration_ms: 0,
+                },
+            );
+        }
+    }
+}
+
+fn is_stage_applicable(stage: DiagnosticStage, connection: &ConnectionDescription) -> bool {
+    return match stage {
+        // Sockets and database files do not require resolving or connecting to a host
+        DiagnosticStage::NameResolution | DiagnosticStage::TcpConnection => {
+            connection.has_server()
+                && (connection.ssh_tunnel.is_some() || connection.socket_path.is_none())
+        }
+        DiagnosticStage::SshHandshake | DiagnosticStage::SshAuthentication => {
+            connection.ssh_tunnel.is_some()
+        }
+        DiagnosticStage::RemoteCli => connection
+            .ssh_tunnel
+            .as_ref()
+            .is_some_and(|ssh_tunnel| ssh_tunnel.mode == SshTunnelMode::RemoteCli),
+        DiagnosticStage::DatabaseLogin
+        | DiagnosticStage::SchemaVersion
+        | DiagnosticStage::MemberTable => true,
+    };
+}
+
+fn get_authentication_method_name(authentication: &SshAuthentication) -> &'static str {
+    return match authentication {
+        SshAuthentication::Password(_) => "password",
+        SshAuthentication::PrivateKey { .. } => "private key",
+        SshAuthentication::Agent => "SSH agent",
+    };
+}
+
+async fn resolve_host(
+    host: &str,
+    port: u16,
+    timeouts: &TimeoutOptions,
+) -> Result<(Vec<SocketAddr>, String), String> {
+    let timeout_result = timeout(timeouts.get_connect_timeout(), lookup_host((host, port))).await;
+
+    if timeout_result.is_err() {
+        return Err(format!(
+            "Resolving '{}' timed out after {} seconds",
+            host, timeouts.connect_seconds
+        ));
+    }
+
+    let lookup_result = timeout_result.unwrap();
+
+    if lookup_result.is_err() {
+        return Err(format!(
+            "Could not resolve '{}' due '{}'. Check the spelling of the host.",
+            host,
+            lookup_result.err().unwrap()
+        ));
+    }
+
+    let addresses: Vec<SocketAddr> = lookup_result.unwrap().collect();
+
+    if addresses.is_empty() {
+        return Err(format!("'{}' does not have any address", host));
+    }
+
+    let details = format!(
+        "Resolved to {}",
+        addresses
+            .iter()
+            .map(|address| address.ip().to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    Ok((addresses, details))
+}
+
+async fn connect_tcp(
+    addresses: &[SocketAddr],
+    timeouts: &TimeoutOptions,
+) -> Result<((), String), String> {
+    let timeout_result = timeout(
+        timeouts.get_connect_timeout(),
+        TcpStream::connect(addresses),
+    )
+    .await;
+
+    if timeout_result.is_err() {
+        return Err(format!(
+            "Connecting timed out after {} seconds. A firewall may block the connection.",
+            timeouts.connect_seconds
+        ));
+    }
+
+    let connect_result = timeout_result.unwrap();
+
+    if connect_result.is_err() {
+        let error = connect_result.err().unwrap();
+        let hint = match error.kind() {
+            ErrorKind::ConnectionRefused => " Nothing listens on the port. Check the port.",
+            ErrorKind::TimedOut => " A firewall may block the connection.",
+            _ => "",
+        };
+
+        return Err(format!("Could not connect due '{}'.{}", error, hint));
+    }
+
+    let details = match connect_result.unwrap().peer_addr() {
+        Ok(peer_address) => format!("Connected to {}", peer_address),
+        Err(_) => String::from("Connected"),
+    };
+
+    Ok(((), details))
+}
+
+// Resolve the host and check whether its port is reachable
+async fn check_reachability(
+    report: &mut ConnectionReport,
+    host: &str,
+    port: u16,
+    timeouts: &TimeoutOptions,
+) -> Option<()> {
+    let addresses = report
+        .run_stage(
+            DiagnosticStage::NameResolution,
+            host.to_owned(),
+            async || resolve_host(host, port, timeouts).await,
+        )
+        .await?;
+
+    report
+        .run_stage(
+            DiagnosticStage::TcpConnection,
+            format!("{}:{}", host, port),
+            async || connect_tcp(&addresses, timeouts).await,
+        )
+        .await
+}
+
+async fn perform_ssh_handshake(
+    hop: &SshHop,
+    opt_jump_session: Option<Arc<client::Handle<SshClient>>>,
+    timeouts: &TimeoutOptions,
+) -> Result<(client::Handle<SshClient>, String), String> {
+    let (connection_result, opt_observation) =
+        connect_ssh_client(&hop.host, hop.port, opt_jump_session, timeouts).await;
+
+    return match (connection_result, opt_observation) {
+        (Ok(ssh_session), Some((_, public_key))) => Ok((
+            ssh_session,
+            format!("Host key '{}' is trusted", get_fingerprint(&public_key)),
+        )),
+        (Ok(ssh_session), None) => Ok((ssh_session, String::from("Handshake completed"))),
+        (Err(_), Some((HostKeyStatus::Unknown { fingerprint }, _))) => Err(format!(
+            "Host key '{}' is unknown and has to be accepted before connecting",
+            fingerprint
+        )),
+        (
+            Err(_),
+            Some((
+                HostKeyStatus::Mismatch {
+                    fingerprint,
+                    known_hosts_line,
+                    ..
+                },
+                _,
+            )),
+        ) => Err(format!(
+            "Host key '{}' does not match the known key in line {} of the known hosts. The connection may be \
+             intercepted.",
+            fingerprint, known_hosts_line
+        )),
+        (Err(_), Some((HostKeyStatus::Unverifiable, _))) => Err(String::from(
+            "Could not read the known hosts for verifying the host key",
+        )),
+        (Err(error), _) => Err(format!("SSH handshake failed due '{}'", error)),
+    };
+}
+
+// Connect to every hop through the session of the previous one and return the session of the last hop
+async fn check_ssh_hops(
+    report: &mut ConnectionReport,
+    hops: &[SshHop],
+    timeouts: &TimeoutOptions,
+) -> Option<client::Handle<SshClient>> {
+    let mut opt_jump_session = None;
+
+    for (hop_index, hop) in hops.iter().enumerate() {
+        let target = format!("{}@{}:{}", hop.username, hop.host, hop.port);
+        let jump_session = opt_jump_session.clone();
+
+        let mut ssh_session = report
+            .run_stage(DiagnosticStage::SshHandshake, target.clone(), async || {
+                perform_ssh_handshake(hop, jump_session, timeouts).await
+            })
+            .await?;
+
+        let method_name = get_authentication_method_name(&hop.authentication);
+
+        report
+            .run_stage(DiagnosticStage::SshAuthentication, target, async || {
+                let authentication_result = authenticate_ssh_client(
+                    &mut ssh_session,
+                    &hop.username,
+                    &hop.authentication,
+                    timeouts,
+                )
+                .await;
+
+                if authentication_result.is_err() {
+                    return Err(format!(
+                        "Authenticating user '{}' via {} failed due '{}'",
+                        hop.username,
+                        method_name,
+                        authentication_result.err().unwrap().get_message()
+                    ));
+                }
+
+                Ok(((), format!("Authenticated via {}", method_name)))
+            })
+            .await?;
+
+        if hop_index + 1 == hops.len() {
+            return Some(ssh_session);
+        }
+
+        opt_jump_session = Some(Arc::new(ssh_session));
+    }
+
+    None
+}
+
+// Run the command on the SSH server and return its exit status and stdout
+async fn run_remote_command(
+    ssh_session: &client::Handle<SshClient>,
+    command: &str,
+    timeouts: &TimeoutOptions,
+) -> Result<(u32, String), String> {
+    let channel_result = ssh_session.channel_open_session().await;
+
+    if channel_result.is_err() {
+        return Err(format!(
+            "Could not open SSH channel due '{}'",
+            channel_result.err().unwrap()
+        ));
+    }
+
+    let mut channel = channel_result.unwrap();
+    let exec_result = channel.exec(true, command.as_bytes()).await;
+
+    if exec_result.is_err() {
+        return Err(format!(
+            "Could not execute '{}' due '{}'",
+            command,
+            exec_result.err().unwrap()
+        ));
+    }
+
+    let mut output = vec![];
+    let mut opt_exit_status = None;
+
+    loop {
+        let timeout_result = timeout(timeouts.get_query_timeout(), channel.wait()).await;
+
+        if timeout_result.is_err() {
+            return Err(format!(
+                "'{}' timed out after {} seconds",
+                command, timeouts.query_seconds
+            ));
+        }
+
+        match timeout_result.unwrap() {
+            Some(ChannelMsg::Data { data }) => output.extend_from_slice(&data),
+            Some(ChannelMsg::ExitStatus { exit_status }) => opt_exit_status = Some(exit_status),
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    if opt_exit_status.is_none() {
+        return Err(format!("'{}' did not report any exit status", command));
+    }
+
+    Ok((
+        opt_exit_status.unwrap(),
+        String::from_utf8_lossy(&output).trim().to_owned(),
+    ))
+}
+
+fn find_cli_program(sql_login_command: &str) -> Option<&str> {
+    sql_login_command.split_whitespace().find(|token| {
+        let program_name = token.rsplit('/').next().unwrap_or_default();
+        CLI_PROGRAMS.contains(&program_name)
+    })
+}
+
+// Return the login command if the CLI of the database is installed on the SSH server
+async fn check_remote_cli(
+    ssh_session: &client::Handle<SshClient>,
+    connection: &ConnectionDescription,
+) -> Result<(String, String), String> {
+    let opt_sql_login_command = render_command_template(connection);
+
+    if opt_sql_login_command.is_none() {
+        return Err(String::from(
+            "The command template is invalid. It has to contain '{output_options}' and only known placeholders.",
+        ));
+    }
+
+    let sql_login_command = opt_sql_login_command.unwrap();
+    let opt_program = find_cli_program(&sql_login_command).map(ToOwned::to_owned);
+
+    if opt_program.is_none() {
+        return Ok((
+            sql_login_command,
+            String::from(
+                "The command template does not call any known CLI. Its availability is not checked.",
+            ),
+        ));
+    }
+
+    let program = opt_program.unwrap();
+    let (exit_status, program_path) = run_remote_command(
+        ssh_session,
+        &format!("command -v {}", quote_for_shell(&program)),
+        &connection.timeouts,
+    )
+    .await?;
+
+    if exit_status != 0 {
+        return Err(format!(
+            "'{}' is not installed on the SSH server or not in the PATH of the SSH user",
+            program
+        ));
+    }
+
+    Ok((
+        sql_login_command,
+        format!("'{}' is available at '{}'", program, program_path),
+    ))
+}
+
+// The ORM connects on establishing whereas the CLI logs in on its first statement
+async fn verify_login(connection: &mut DbConnection) -> bool {
+    let select_statement = Query::select().expr_as(Expr::val(1), "value").to_owned();
+
+    connection
+        .load_integers(select_statement)
+        .await
+        .is_ok_and(|values| values == [1])
+}
+
+async fn establish_orm_login(
+    connection: &ConnectionDescription,
+    host: &str,
+    port: u16,
+) -> Result<OrmConnection, String> {
+    try_establish_orm_connection(connection, host, port)
+        .await
+        .map_err(|error| format!("Logging in failed due '{}'", error.get_message()))
+}
+
+async fn check_login(
+    report: &mut ConnectionReport,
+    connection: &ConnectionDescription,
+    opt_ssh_session: Option<client::Handle<SshClient>>,
+) -> Option<DbConnection> {
+    let is_remote_cli = connection
+        .ssh_tunnel
+        .as_ref()
+        .is_some_and(|ssh_tunnel| ssh_tunnel.mode == SshTunnelMode::RemoteCli);
+    let mut sql_login_command = String::new();
+
+    if is_remote_cli {
+        sql_login_command = report
+            .run_stage(
+                DiagnosticStage::RemoteCli,
+                connection.ssh_tunnel.as_ref().unwrap().host.clone(),
+                async || check_remote_cli(opt_ssh_session.as_ref().unwrap(), connection).await,
+            )
+            .await?;
+    }
+
+    let target = if connection.has_server() {
+        format!(
+            "{}@{}",
+            connection.user,
+            connection.socket_path.as_ref().unwrap_or(&connection.host)
+        )
+    } else {
+        connection.name.clone()
+    };
+
+    report
+        .run_stage(DiagnosticStage::DatabaseLogin, target, async move || {
+            let mut db_connection = match opt_ssh_session {
+                None => DbConnection::OrmBased(
+                    establish_orm_login(connection, &connection.host, connection.port).await?,
+                    None,
+                    None,
+                ),
+                Some(ssh_session) if is_remote_cli => DbConnection::SshBased(SshConnection::new(
+                    ssh_session,
+                    sql_login_command.clone(),
+                    connection.password.clone(),
+                    connection.backend.clone(),
+                    connection.timeouts.get_query_timeout(),
+                )),
+                Some(ssh_session) => {
+                    let opt_port_forwarding = start_port_forwarding(
+                        ssh_session,
+                        connection.host.clone(),
+                        connection.port,
+                    )
+                    .await;
+
+                    if opt_port_forwarding.is_none() {
+                        return Err(format!(
+                            "Could not forward a local port to '{}:{}' via the SSH server",
+                            connection.host, connection.port
+                        ));
+                    }
+
+                    let port_forwarding = opt_port_forwarding.unwrap();
+                    let orm_connection = establish_orm_login(
+                        connection,
+                        "127.0.0.1",
+                        port_forwarding.get_local_port(),
+                    )
+                    .await?;
+
+                    DbConnection::OrmBased(orm_connection, Some(port_forwarding), None)
+                }
+            };
+
+            if !verify_login(&mut db_connection).await {
+                return Err(if is_remote_cli {
+                    format!(
+                        "Logging in via '{}' failed. Check the user, the password and the command template.",
+                        sql_login_command
+                    )
+                } else {
+                    String::from("Logged in but could not run any query")
+                });
+            }
+
+            Ok((
+                db_connection,
+                format!("Logged in to database '{}'", connection.name),
+            ))
+        })
+        .await
+}
+
+async fn connect_in_stages(
+    report: &mut ConnectionReport,
+    connection: &ConnectionDescription,
+) -> Option<DbConnection> {
+    if connection.ssh_tunnel.is_none() {
+        if connection.socket_path.is_none() && connection.has_server() {
+            check_reachability(
+                report,
+                &connection.host,
+                connection.port,
+                &connection.timeouts,
+            )
+            .await?;
+        }
+
+        return check_login(report, connection, None).await;
+    }
+
+    if !connection.has_server() {
+        report.record_stage(
+            DiagnosticStage::DatabaseLogin,
+            connection.name.clone(),
+            StageOutcome::Failed {
+                reason: String::from(
+                    "Database files can only be opened locally without SSH tunnel",
+                ),
+            },
+            Instant::now(),
+        );
+        return None;
+    }
+
+    if connection.socket_path.is_some() {
+        report.record_stage(
+            DiagnosticStage::DatabaseLogin,
+            connection.socket_path.clone().unwrap(),
+            StageOutcome::Failed {
+                reason: String::from(
+                    "Unix sockets are only supported for connections without SSH tunnel",
+                ),
+            },
+            Instant::now(),
+        );
+        return None;
+    }
+
+    let hops = connection.ssh_tunnel.as_ref().unwrap().get_hops();
+
+    check_reachability(report, &hops[0].host, hops[0].port, &connection.timeouts).await?;
+    let ssh_session = check_ssh_hops(report, &hops, &connection.timeouts).await?;
+
+    check_login(report, connection, Some(ssh_session)).await
+}
+
+// SQLite lists its tables in sqlite_master instead of the information schema
+#[cfg(feature = "sqlite")]
+async fn sqlite_table_exists(connection: &mut DbConnection, table_name: &str) -> Option<bool> {
+    let select_statement = Query::select()
+        .expr_as(Expr::val(1), "value")
+        .from("sqlite_master")
+        .and_where(Expr::col("type").eq("table"))
+        .and_where(Expr::col("name").eq(table_name))
+        .to_owned();
+
+    connection
+        .load_integers(select_statement)
+        .await
+        .map(|values| !values.is_empty())
+        .ok()
+}
+
+// Servers list the tables of all schemas in the information schema
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+async fn server_table_exists(
+    connection: &mut DbConnection,
+    current_schema: &'static str,
+    table_name: &str,
+) -> Option<bool> {
+    let select_statement = Query::select()
+        .expr_as(Expr::val(1), "value")
+        .from(("information_schema", "tables"))
+        .and_where(Expr::col("table_schema").eq(Expr::cust(current_schema)))
+        .and_where(Expr::col("table_name").eq(table_name))
+        .to_owned();
+
+    connection
+        .load_integers(select_statement)
+        .await
+        .map(|values| !values.is_empty())
+        .ok()
+}
+
+// Check the existence of the table without requiring privileges on it
+async fn table_exists(connection: &mut DbConnection, table_name: &str) -> Option<bool> {
+    return match connection.get_backend() {
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => {
+            server_table_exists(connection, "current_schema()", table_name).await
+        }
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => server_table_exists(connection, "DATABASE()", table_name).await,
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => sqlite_table_exists(connection, table_name).await,
+    };
+}
+
+// In contrast to determining the schema version for upgrading this does not create the version table
+async fn check_schema_version(report: &mut ConnectionReport, connection: &mut DbConnection) {
+    let start = Instant::now();
+    let latest_version = get_latest_version();
+
+    let outcome = match table_exists(connection, "schema_version").await {
+        None => StageOutcome::Failed {
+            reason: String::from("Could not query the tables of the database"),
+        },
+        Some(false) => StageOutcome::Warning {
+            details: format!(
+                "The database is not initialized yet. Upgrading to schema version {} is required.",
+                latest_version
+            ),
+        },
+        Some(true) => {
+            let select_statement = Query::select()
+                .expr_as(Expr::col("version"), "value")
+                .from("schema_version")
+                .to_owned();
+
+            match connection.load_integers(select_statement).await {
+                Err(_) => StageOutcome::Failed {
+                    reason: String::from("Could not read table 'schema_version'"),
+                },
+                Ok(versions) => {
+                    let version = versions.into_iter().max().unwrap_or(0);
+
+                    if version < latest_version {
+                        StageOutcome::Warning {
+                            details: format!(
+                                "Schema version {} is outdated. Upgrading to version {} is required.",
+                                version, latest_version
+                            ),
+                        }
+                    } else if version > latest_version {
+                        StageOutcome::Failed {
+                            reason: format!(
+                                "Schema version {} is newer than the supported version {}. Update the application.",
+                                version, latest_version
+                            ),
+                        }
+                    } else {
+                        StageOutcome::Passed {
+                            details: format!("Schema version {} is up to date", version),
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    report.record_stage(
+        DiagnosticStage::SchemaVersion,
+        String::from("schema_version"),
+        outcome,
+        start,
+    );
+}
+
+async fn check_member_table(report: &mut ConnectionReport, connection: &mut DbConnection) {
+    report
+        .run_stage(
+            DiagnosticStage::MemberTable,
+            String::from("member"),
+            async || {
+                if !table_exists(connection, "member").await.unwrap_or(false) {
+                    return Err(String::from(
+                        "Table 'member' does not exist. Upgrading the schema creates it.",
+                    ));
+                }
+
+                let select_statement = Query::select()
+                    .expr_as(Expr::val(1), "value")
+                    .from("member")
+                    .limit(1)
+                    .to_owned();
+
+                return match connection.load_integers(select_statement).await {
+                    Err(_) => Err(String::from(
+                        "Table 'member' is not readable. Check the privileges of the database user.",
+                    )),
+                    Ok(values) if values.is_empty() => {
+                        Ok(((), String::from("Table 'member' is readable but empty")))
+                    }
+                    Ok(_) => Ok(((), String::from("Table 'member' is readable"))),
+                };
+            },
+        )
+        .await;
+}
+
+/* NOTE 2026-10-18: Has to run on RUNTIME since diesel blocks the current thread while the SSH session and the port
+ * forwarding proceed in tasks of the runtime.
+ */
+pub(crate) async fn test_connection_impl(connection: &ConnectionDescription) -> ConnectionReport {
+    let mut report = ConnectionReport { stages: vec![] };
+
+    if let Some(mut db_connection) = connect_in_stages(&mut report, connection).await {
+        check_schema_version(&mut report, &mut db_connection).await;
+        check_member_table(&mut report, &mut db_connection).await;
+    }
+
+    report.add_skipped_stages(connection);
+    report
+}
+
+/* Run every stage of connecting separately without using or affecting the managed connections. Stages following a
+ * failed one are skipped.
+ */
+pub fn test_connection(connection: ConnectionDescription) -> ConnectionReport {
+    RUNTIME.block_on(test_connection_impl(&connection))
+}
+
+#[cfg(test)]
+mod test {
+    use database_types::connection_description::TlsOptions;
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+
+    use speculoos::prelude::*;
+
+    use crate::connection::get_connection;
+    use crate::test_database_common::{self, GetCurrentDBName, create_member_tables};
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> ConnectionDescription
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        test_database_common::setup_test_description(sqlx_pool).await
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    fn get_outcome(report: &ConnectionReport, stage: DiagnosticStage) -> StageOutcome {
+        report
+            .stages
+            .iter()
+            .find(|stage_report| stage_report.stage == stage)
+            .map(|stage_report| stage_report.outcome.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_find_cli_program() {
+        assert_that!(find_cli_program(
+            "sudo -u postgres /usr/lib/postgresql/17/bin/psql --csv -d 'members'"
+        ))
+        .is_equal_to(Some("/usr/lib/postgresql/17/bin/psql"));
+        assert_that!(find_cli_program("mariadb --batch members")).is_equal_to(Some("mariadb"));
+        assert_that!(find_cli_program("docker exec db db-shell --csv")).is_none();
+    }
+
+    #[test]
+    fn test_add_skipped_stages() {
+        let connection = ConnectionDescription {
+            backend: get_default_backend(),
+            host: String::from("db.example.org"),
+            port: 5432,
+            user: String::from("admin"),
+            password: String::new(),
+            name: String::from("members"),
+            ssh_tunnel: None,
+            tls: TlsOptions::default(),
+            socket_path: None,
+            cli_command_template: None,
+            timeouts: TimeoutOptions::default(),
+        };
+
+        let mut report = ConnectionReport { stages: vec![] };
+        report.record_stage(
+            DiagnosticStage::TcpConnection,
+            String::from("db.example.org:5432"),
+            StageOutcome::Failed {
+                reason: String::from("Connection refused"),
+            },
+            Instant::now(),
+        );
+        report.add_skipped_stages(&connection);
+
+        let stages: Vec<DiagnosticStage> = report
+            .stages
+            .iter()
+            .map(|stage_report| stage_report.stage)
+            .collect();
+        assert_that!(stages).is_equal_to(STAGES.to_vec());
+        assert_that!(get_outcome(&report, DiagnosticStage::SshHandshake)).is_equal_to(
+            StageOutcome::Skipped {
+                reason: String::from("Not applicable to the settings of the connection"),
+            },
+        );
+        assert_that!(get_outcome(&report, DiagnosticStage::DatabaseLogin)).is_equal_to(
+            StageOutcome::Skipped {
+                reason: String::from("Requires the failed stage TcpConnection"),
+            },
+        );
+    }
+
+    fn get_default_backend() -> DatabaseBackend {
+        #[cfg(feature = "postgres")]
+        return DatabaseBackend::PostgreSql;
+        #[cfg(all(not(feature = "postgres"), feature = "mysql"))]
+        return DatabaseBackend::MySql;
+        #[cfg(not(any(feature = "postgres", feature = "mysql")))]
+        return DatabaseBackend::Sqlite;
+    }
+
+    async fn test_test_connection(connection: ConnectionDescription) -> sqlx::Result<()> {
+        let report = test_connection_impl(&connection).await;
+
+        assert_that!(get_outcome(&report, DiagnosticStage::DatabaseLogin))
+            .matches(|outcome| matches!(outcome, StageOutcome::Passed { .. }));
+        assert_that!(get_outcome(&report, DiagnosticStage::SchemaVersion))
+            .matches(|outcome| matches!(outcome, StageOutcome::Warning { .. }));
+        assert_that!(get_outcome(&report, DiagnosticStage::MemberTable))
+            .matches(|outcome| matches!(outcome, StageOutcome::Failed { .. }));
+
+        let mut db_connection = get_connection(connection.clone()).await.unwrap();
+        create_member_tables(&mut db_connection).await;
+
+        let report = test_connection_impl(&connection).await;
+
+        assert_that!(report.stages.iter().all(|stage_report| matches!(
+            stage_report.outcome,
+            StageOutcome::Passed { .. } | StageOutcome::Skipped { .. }
+        )))
+        .is_true();
+        assert_that!(get_outcome(&report, DiagnosticStage::DatabaseLogin))
+            .matches(|outcome| matches!(outcome, StageOutcome::Passed { .. }));
+
+        // The missing member table of the first report
+        tear_down(1);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_test_connection_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_test_connection(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_test_connection_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_test_connection(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_test_connection_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_test_connection(setup_test(pool).await).await
+    }
+}
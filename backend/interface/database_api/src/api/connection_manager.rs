@@ -0,0 +1,499 @@
+use crate::api::database_error::DatabaseError;
+use crate::connection::get_connection;
+use crate::db_connection::{DbConnection, StatementCanceller, StatementWatchdog};
+use database_types::connection_description::ConnectionDescription;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, LazyLock};
+use tokio::runtime::Runtime;
+use tokio::sync::Mutex;
+
+// Slot of a connection which is empty until connected
+struct ManagedConnection {
+    opt_connection: Mutex<Option<DbConnection>>,
+    // Cancels statements of the connection while the connection itself is locked by the running action
+    canceller_result: std::sync::Mutex<Result<Arc<StatementCanceller>, DatabaseError>>,
+}
+
+type SharedConnection = Arc<ManagedConnection>;
+
+// Establishing connections (especially via SSH) is expensive. Therefore connections are kept until closed explicitly.
+pub(crate) static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| Runtime::new().unwrap());
+static CONNECTIONS: LazyLock<Mutex<HashMap<u64, SharedConnection>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Descriptions contain credentials which should not be kept in plain text
+fn get_connection_key(description: &ConnectionDescription) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    description.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Only the slot is locked while connecting such that connecting to one database does not block others
+async fn get_shared_connection(description: &ConnectionDescription) -> SharedConnection {
+    CONNECTIONS
+        .lock()
+        .await
+        .entry(get_connection_key(description))
+        .or_insert_with(|| {
+            Arc::new(ManagedConnection {
+                opt_connection: Mutex::new(None),
+                canceller_result: std::sync::Mutex::new(Err(DatabaseError::Connection {
+                    message: String::from("Not connected yet"),
+                })),
+            })
+        })
+        .clone()
+}
+
+async fn remove_shared_connection(
+    description: &ConnectionDescription,
+    shared_connection: &SharedConnection,
+) {
+    let mut connections = CONNECTIONS.lock().await;
+    let connection_key = get_connection_key(description);
+
+    // The slot may have been replaced after closing the connection in the meantime
+    if connections
+        .get(&connection_key)
+        .is_some_and(|current_connection| Arc::ptr_eq(current_connection, shared_connection))
+    {
+        connections.remove(&connection_key);
+    }
+}
+
+// Connections whose statements cannot be cancelled are usable nonetheless
+async fn establish_connection(
+    description: &ConnectionDescription,
+) -> Result<(DbConnection, Result<Arc<StatementCanceller>, DatabaseError>), DatabaseError> {
+    let mut connection = get_connection(description.clone()).await?;
+    let canceller_result = connection
+        .create_statement_canceller(description)
+        .await
+        .map(Arc::new);
+
+    match &canceller_result {
+        Ok(canceller) => connection.set_statement_watchdog(StatementWatchdog::new(
+            canceller.clone(),
+            description.timeouts.get_query_timeout(),
+        )),
+        // Local database files have no server session whose statements could be cancelled
+        Err(DatabaseError::Unsupported { .. }) => {}
+        Err(error) => warn!(
+            "Statements on database '{}' cannot be cancelled due '{}'",
+            description.name, error
+        ),
+    }
+
+    Ok((connection, canceller_result))
+}
+
+pub(crate) async fn with_connection_impl<ResultType>(
+    description: &ConnectionDescription,
+    action: impl AsyncFnOnce(&mut DbConnection) -> Result<ResultType, DatabaseError>,
+) -> Result<ResultType, DatabaseError> {
+    let shared_connection = get_shared_connection(description).await;
+    let mut opt_connection = shared_connection.opt_connection.lock().await;
+
+    let is_connected = match opt_connection.as_mut() {
+        Some(connection) => connection.is_healthy().await,
+        None => false,
+    };
+
+    if !is_connected {
+        if opt_connection.is_some() {
+            warn!(
+                "Connection to database '{}' is broken. Reconnecting...",
+                description.name
+            );
+        } else {
+            info!("Connecting to database '{}'...", description.name);
+        }
+
+        let connection_result = establish_connection(description).await;
+
+        if connection_result.is_err() {
+            error!("Could not establish connection");
+            *opt_connection = None;
+            remove_shared_connection(description, &shared_connection).await;
+            return Err(connection_result.err().unwrap());
+        }
+
+        let (connection, canceller_result) = connection_result.unwrap();
+        *opt_connection = Some(connection);
+        *shared_connection.canceller_result.lock().unwrap() = canceller_result;
+    }
+
+    action(opt_connection.as_mut().unwrap()).await
+}
+
+// Run the action on the managed connection of the description while reconnecting broken connections
+pub(crate) fn with_connection<ResultType>(
+    description: ConnectionDescription,
+    action: impl AsyncFnOnce(&mut DbConnection) -> Result<ResultType, DatabaseError>,
+) -> Result<ResultType, DatabaseError> {
+    RUNTIME.block_on(with_connection_impl(&description, action))
+}
+
+// Connect if not connected yet and verify that the connection is usable
+pub fn check_connection(connection: ConnectionDescription) -> Result<(), DatabaseError> {
+    with_connection(connection, async |_connection| Ok(()))
+}
+
+async fn close_connection_impl(connection: &ConnectionDescription) -> bool {
+    let opt_connection = CONNECTIONS
+        .lock()
+        .await
+        .remove(&get_connection_key(connection));
+
+    if opt_connection.is_none() {
+        warn!(
+            "There is no open connection to database '{}'",
+            connection.name
+        );
+        return false;
+    }
+
+    true
+}
+
+// Queries which are currently running on the connection are completed before the connection is closed
+pub fn close_connection(connection: ConnectionDescription) -> bool {
+    RUNTIME.block_on(close_connection_impl(&connection))
+}
+
+async fn cancel_running_statement_impl(
+    connection: &ConnectionDescription,
+) -> Result<(), DatabaseError> {
+    let opt_shared_connection = CONNECTIONS
+        .lock()
+        .await
+        .get(&get_connection_key(connection))
+        .cloned();
+
+    if opt_shared_connection.is_none() {
+        let message = format!(
+            "There is no open connection to database '{}'",
+            connection.name
+        );
+        warn!("{}", message);
+        return Err(DatabaseError::NotFound { message });
+    }
+
+    let canceller_result = opt_shared_connection
+        .unwrap()
+        .canceller_result
+        .lock()
+        .unwrap()
+        .clone();
+
+    if canceller_result.is_err() {
+        error!(
+            "Cannot cancel statements on database '{}' due '{}'",
+            connection.name,
+            canceller_result.as_ref().err().unwrap()
+        );
+        return Err(canceller_result.err().unwrap());
+    }
+
+    info!(
+        "Cancelling running statement on database '{}'",
+        connection.name
+    );
+    canceller_result.unwrap().cancel().await
+}
+
+/* Abort the statement which currently runs on the managed connection. The action running it fails as if the statement
+ * failed. Has to be called from a different thread than the one waiting for the action.
+ */
+pub fn cancel_running_statement(connection: ConnectionDescription) -> Result<(), DatabaseError> {
+    RUNTIME.block_on(cancel_running_statement_impl(&connection))
+}
+
+pub fn close_all_connections() {
+    RUNTIME.block_on(async { CONNECTIONS.lock().await.clear() });
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    use std::time::{Duration, Instant};
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    use tokio::time::sleep;
+
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    use database_types::connection_description::DatabaseBackend;
+    use speculoos::prelude::*;
+
+    use crate::test_database_common::{self, GetCurrentDBName};
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> ConnectionDescription
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        test_database_common::setup_test_description(sqlx_pool).await
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    // NOTE MySQL reports an error for interrupted sleeps only if the sleep is part of a query reading rows
+    fn create_sleep_statement(description: &ConnectionDescription, seconds: f32) -> String {
+        return match description.backend {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => format!("SELECT pg_sleep({})", seconds),
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => format!(
+                "SELECT SLEEP({}) FROM (SELECT 1 UNION ALL SELECT 2) AS sleeping LIMIT 1",
+                seconds
+            ),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => unreachable!("SQLite has no server session"),
+        };
+    }
+
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    async fn test_cancel_server_statement(
+        description: ConnectionDescription,
+        expected_num_severe_messages: usize,
+    ) -> sqlx::Result<()> {
+        let mut connection = get_connection(description.clone()).await.unwrap();
+        let canceller = connection
+            .create_statement_canceller(&description)
+            .await
+            .unwrap();
+
+        let cancellation = RUNTIME.spawn(async move {
+            sleep(Duration::from_secs(1)).await;
+            canceller.cancel().await
+        });
+
+        let start = Instant::now();
+        let statement_result = connection
+            .execute_sql(create_sleep_statement(&description, 30.0))
+            .await;
+
+        assert_that!(start.elapsed()).is_less_than(Duration::from_secs(20));
+        assert_that!(cancellation.await.unwrap()).is_ok();
+
+        assert_that!(statement_result.is_err()).is_true();
+
+        // The CLIs do not report why a statement failed
+        if matches!(connection, DbConnection::OrmBased(..)) {
+            assert_that!(statement_result.err().unwrap().code())
+                .is_equal_to(String::from("cancelled"));
+        }
+
+        tear_down(expected_num_severe_messages);
+        Ok(())
+    }
+
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    async fn test_statement_timeout(
+        mut description: ConnectionDescription,
+        expected_num_severe_messages: usize,
+    ) -> sqlx::Result<()> {
+        description.timeouts.query_seconds = 2;
+
+        // The query timeout applies to each statement instead of the whole action
+        let start = Instant::now();
+        let statements_result = with_connection_impl(&description, async |connection| {
+            connection
+                .execute_sql(create_sleep_statement(&description, 1.5))
+                .await?;
+            connection
+                .execute_sql(create_sleep_statement(&description, 1.5))
+                .await
+        })
+        .await;
+        assert_that!(statements_result).is_ok();
+        assert_that!(start.elapsed()).is_greater_than(Duration::from_secs(2));
+
+        let start = Instant::now();
+        let statement_result = with_connection_impl(&description, async |connection| {
+            connection
+                .execute_sql(create_sleep_statement(&description, 30.0))
+                .await
+        })
+        .await;
+        assert_that!(start.elapsed()).is_less_than(Duration::from_secs(20));
+        assert_that!(statement_result.is_err()).is_true();
+        assert_that!(statement_result.err().unwrap().code()).is_equal_to(String::from("timeout"));
+
+        close_connection_impl(&description).await;
+
+        tear_down(expected_num_severe_messages);
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn test_unsupported_cancellation(description: ConnectionDescription) -> sqlx::Result<()> {
+        let cancel_result = cancel_running_statement_impl(&description).await;
+        assert_that!(cancel_result.map_err(|error| error.code()))
+            .is_equal_to(Err(String::from("not_found")));
+
+        assert_that!(with_connection_impl(&description, async |_connection| Ok(())).await).is_ok();
+
+        let cancel_result = cancel_running_statement_impl(&description).await;
+        assert_that!(cancel_result.map_err(|error| error.code()))
+            .is_equal_to(Err(String::from("unsupported")));
+
+        close_connection_impl(&description).await;
+
+        // No open connection and unsupported cancellation
+        tear_down(2);
+        Ok(())
+    }
+
+    async fn test_reuse_and_close(description: ConnectionDescription) -> sqlx::Result<()> {
+        // Temporary tables are only visible within the connection creating them
+        let creation_result = with_connection_impl(&description, async |connection| {
+            connection
+                .execute_sql(String::from(
+                    "CREATE TEMPORARY TABLE reused (value integer)",
+                ))
+                .await
+        })
+        .await;
+        assert_that!(creation_result).is_ok();
+
+        let insertion_statement = "INSERT INTO reused (value) VALUES (1)";
+        let insertion_result = with_connection_impl(&description, async |connection| {
+            connection
+                .execute_sql(String::from(insertion_statement))
+                .await
+        })
+        .await;
+        assert_that!(insertion_result).is_ok_containing(1);
+
+        assert_that!(close_connection_impl(&description).await).is_true();
+        assert_that!(close_connection_impl(&description).await).is_false();
+
+        let reconnected_insertion_result = with_connection_impl(&description, async |connection| {
+            connection
+                .execute_sql(String::from(insertion_statement))
+                .await
+        })
+        .await;
+        assert_that!(reconnected_insertion_result).is_err();
+        close_connection_impl(&description).await;
+
+        // Closing a closed connection and inserting into the table of the closed connection
+        tear_down(2);
+        Ok(())
+    }
+
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    async fn test_reconnect(description: ConnectionDescription) -> sqlx::Result<()> {
+        let server_session_id = with_connection_impl(&description, async |connection| {
+            connection.get_server_session_id().await
+        })
+        .await
+        .unwrap();
+
+        let termination_statement = match description.backend {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => {
+                format!("SELECT pg_terminate_backend({})", server_session_id)
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => format!("KILL CONNECTION {}", server_session_id),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => unreachable!("SQLite has no server session"),
+        };
+        let mut terminating_connection = get_connection(description.clone()).await.unwrap();
+        assert_that!(
+            terminating_connection
+                .execute_sql(termination_statement)
+                .await
+        )
+        .is_ok();
+
+        let reconnected_server_session_id =
+            with_connection_impl(&description, async |connection| {
+                connection.get_server_session_id().await
+            })
+            .await;
+        assert_that!(reconnected_server_session_id)
+            .is_ok()
+            .is_not_equal_to(server_session_id);
+
+        close_connection_impl(&description).await;
+
+        // The failed health check of the terminated connection and the reconnection
+        tear_down(2);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_reuse_and_close_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_reuse_and_close(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_reuse_and_close_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_reuse_and_close(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_reuse_and_close_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_reuse_and_close(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_reconnect_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_reconnect(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_reconnect_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_reconnect(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_cancel_server_statement_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_cancel_server_statement(setup_test(pool).await, 1).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_cancel_server_statement_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_cancel_server_statement(setup_test(pool).await, 0).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_unsupported_cancellation_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_unsupported_cancellation(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_statement_timeout_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_statement_timeout(setup_test(pool).await, 1).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_statement_timeout_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_statement_timeout(setup_test(pool).await, 0).await
+    }
+}
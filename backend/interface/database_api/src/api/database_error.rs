@@ -0,0 +1,282 @@
+use diesel::ConnectionError;
+use diesel::result::DatabaseErrorKind;
+use flutter_rust_bridge::frb;
+use std::fmt;
+use std::io::ErrorKind;
+
+// Messages of the servers when a running statement is aborted via pg_cancel_backend() or KILL QUERY
+const CANCELLATION_MESSAGES: [&str; 2] = ["canceling statement", "query execution was interrupted"];
+
+const AUTHENTICATION_MESSAGES: [&str; 3] = [
+    "password authentication failed",
+    "no password supplied",
+    "access denied for user",
+];
+
+// Failures of database operations which are thrown as exceptions in Dart
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseError {
+    Connection {
+        message: String,
+    },
+    Authentication {
+        message: String,
+    },
+    Ssh {
+        message: String,
+    },
+    // The fingerprint has to be confirmed by the user before connecting
+    HostKeyUnknown {
+        message: String,
+        fingerprint: String,
+    },
+    // The connection may be intercepted
+    HostKeyMismatch {
+        message: String,
+        known_fingerprint: String,
+        presented_fingerprint: String,
+    },
+    QuerySyntax {
+        message: String,
+    },
+    ConstraintViolation {
+        message: String,
+    },
+    TypeConversion {
+        message: String,
+    },
+    Timeout {
+        message: String,
+    },
+    // The running statement was aborted on request
+    Cancelled {
+        message: String,
+    },
+    // Records which do not exist (anymore)
+    NotFound {
+        message: String,
+    },
+    // Arguments or settings which are rejected before contacting the database
+    InvalidInput {
+        message: String,
+    },
+    // Statements which succeeded but did not have the expected effect
+    UnexpectedResult {
+        message: String,
+    },
+    // Operations which the backend of the database does not provide
+    Unsupported {
+        message: String,
+    },
+}
+
+impl DatabaseError {
+    // Stable identifier of the kind of error which the UI can localize
+    #[frb(sync)]
+    pub fn code(&self) -> String {
+        let code = match self {
+            Self::Connection { .. } => "connection",
+            Self::Authentication { .. } => "authentication",
+            Self::Ssh { .. } => "ssh",
+            Self::HostKeyUnknown { .. } => "host_key_unknown",
+            Self::HostKeyMismatch { .. } => "host_key_mismatch",
+            Self::QuerySyntax { .. } => "query_syntax",
+            Self::ConstraintViolation { .. } => "constraint_violation",
+            Self::TypeConversion { .. } => "type_conversion",
+            Self::Timeout { .. } => "timeout",
+            Self::Cancelled { .. } => "cancelled",
+            Self::NotFound { .. } => "not_found",
+            Self::InvalidInput { .. } => "invalid_input",
+            Self::UnexpectedResult { .. } => "unexpected_result",
+            Self::Unsupported { .. } => "unsupported",
+        };
+
+        String::from(code)
+    }
+
+    #[frb(ignore)]
+    pub fn get_message(&self) -> &str {
+        return match self {
+            Self::Connection { message }
+            | Self::Authentication { message }
+            | Self::Ssh { message }
+            | Self::HostKeyUnknown { message, .. }
+            | Self::HostKeyMismatch { message, .. }
+            | Self::QuerySyntax { message }
+            | Self::ConstraintViolation { message }
+            | Self::TypeConversion { message }
+            | Self::Timeout { message }
+            | Self::Cancelled { message }
+            | Self::NotFound { message }
+            | Self::InvalidInput { message }
+            | Self::UnexpectedResult { message }
+            | Self::Unsupported { message } => message,
+        };
+    }
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} ({})", self.get_message(), self.code())
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+fn contains_any(message: &str, patterns: &[&str]) -> bool {
+    let lowercase_message = message.to_lowercase();
+    patterns
+        .iter()
+        .any(|pattern| lowercase_message.contains(pattern))
+}
+
+impl From<diesel::result::Error> for DatabaseError {
+    fn from(error: diesel::result::Error) -> Self {
+        let message = error.to_string();
+
+        return match error {
+            diesel::result::Error::DatabaseError(kind, _) => match kind {
+                DatabaseErrorKind::UniqueViolation
+                | DatabaseErrorKind::ForeignKeyViolation
+                | DatabaseErrorKind::NotNullViolation
+                | DatabaseErrorKind::CheckViolation => Self::ConstraintViolation { message },
+                DatabaseErrorKind::ClosedConnection | DatabaseErrorKind::UnableToSendCommand => {
+                    Self::Connection { message }
+                }
+                _ if contains_any(&message, &CANCELLATION_MESSAGES) => Self::Cancelled { message },
+                _ => Self::QuerySyntax { message },
+            },
+            diesel::result::Error::DeserializationError(_)
+            | diesel::result::Error::SerializationError(_) => Self::TypeConversion { message },
+            diesel::result::Error::NotFound => Self::NotFound { message },
+            _ => Self::QuerySyntax { message },
+        };
+    }
+}
+
+impl From<ConnectionError> for DatabaseError {
+    fn from(error: ConnectionError) -> Self {
+        let message = error.to_string();
+
+        return match error {
+            ConnectionError::InvalidConnectionUrl(_) => Self::InvalidInput { message },
+            _ if contains_any(&message, &AUTHENTICATION_MESSAGES) => {
+                Self::Authentication { message }
+            }
+            _ => Self::Connection { message },
+        };
+    }
+}
+
+impl From<russh::Error> for DatabaseError {
+    fn from(error: russh::Error) -> Self {
+        let message = error.to_string();
+
+        return match error {
+            russh::Error::IO(io_error) if io_error.kind() == ErrorKind::TimedOut => {
+                Self::Timeout { message }
+            }
+            // The SSH server is not reachable at all
+            russh::Error::IO(_) => Self::Connection { message },
+            _ => Self::Ssh { message },
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_codes_are_distinct() {
+        let message = String::new();
+        let codes: Vec<String> = [
+            DatabaseError::Connection {
+                message: message.clone(),
+            },
+            DatabaseError::Authentication {
+                message: message.clone(),
+            },
+            DatabaseError::Ssh {
+                message: message.clone(),
+            },
+            DatabaseError::HostKeyUnknown {
+                message: message.clone(),
+                fingerprint: String::new(),
+            },
+            DatabaseError::HostKeyMismatch {
+                message: message.clone(),
+                known_fingerprint: String::new(),
+                presented_fingerprint: String::new(),
+            },
+            DatabaseError::QuerySyntax {
+                message: message.clone(),
+            },
+            DatabaseError::ConstraintViolation {
+                message: message.clone(),
+            },
+            DatabaseError::TypeConversion {
+                message: message.clone(),
+            },
+            DatabaseError::Timeout {
+                message: message.clone(),
+            },
+            DatabaseError::Cancelled {
+                message: message.clone(),
+            },
+            DatabaseError::NotFound {
+                message: message.clone(),
+            },
+            DatabaseError::InvalidInput {
+                message: message.clone(),
+            },
+            DatabaseError::UnexpectedResult {
+                message: message.clone(),
+            },
+            DatabaseError::Unsupported { message },
+        ]
+        .iter()
+        .map(DatabaseError::code)
+        .collect();
+
+        let mut distinct_codes = codes.clone();
+        distinct_codes.sort();
+        distinct_codes.dedup();
+
+        assert_that!(distinct_codes.len()).is_equal_to(codes.len());
+    }
+
+    #[test]
+    fn test_from_connection_error() {
+        let error = DatabaseError::from(ConnectionError::BadConnection(String::from(
+            "FATAL:  password authentication failed for user \"member\"",
+        )));
+        assert_that!(error.code()).is_equal_to(String::from("authentication"));
+
+        let error = DatabaseError::from(ConnectionError::BadConnection(String::from(
+            "Access denied for user 'member'@'localhost' (using password: YES)",
+        )));
+        assert_that!(error.code()).is_equal_to(String::from("authentication"));
+
+        let error = DatabaseError::from(ConnectionError::BadConnection(String::from(
+            "Connection refused",
+        )));
+        assert_that!(error.code()).is_equal_to(String::from("connection"));
+    }
+
+    #[test]
+    fn test_from_russh_error() {
+        let error = DatabaseError::from(russh::Error::from(std::io::Error::new(
+            ErrorKind::TimedOut,
+            "Connecting timed out after 10 seconds",
+        )));
+        assert_that!(error.code()).is_equal_to(String::from("timeout"));
+
+        let error = DatabaseError::from(russh::Error::from(std::io::Error::from(
+            ErrorKind::ConnectionRefused,
+        )));
+        assert_that!(error.code()).is_equal_to(String::from("connection"));
+    }
+}
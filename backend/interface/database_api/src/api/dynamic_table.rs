@@ -0,0 +1,913 @@
+use crate::api::connection_manager::with_connection;
+use crate::api::database_error::DatabaseError;
+use crate::api::models;
+use crate::db_connection::DbConnection;
+use crate::json_field_conversion::{FieldType, FieldTypeInfo, JsonFieldConversion, get_field_name};
+use crate::value_conversion::{from_database_json_value, matches_field_type, to_json_value};
+use backend_macros::JsonFieldConversionGenerator;
+use chrono::Local;
+use database_types::column_value::ColumnValue;
+use database_types::connection_description::{ConnectionDescription, DatabaseBackend};
+use diesel::QueryableByName;
+use flutter_rust_bridge::frb;
+use log::error;
+use sea_query::Expr;
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+use sea_query::ExprTrait;
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use sea_query::Func;
+use sea_query::Order;
+use sea_query::Query;
+use sea_query::SelectStatement;
+use serde::{Deserialize, Serialize};
+
+/* Tables of legacy databases (e.g. of Green2) differ from the member table of the migrations. Their columns are
+ * determined at runtime and every row is serialized to a JSON object by the database which works for the ORM and the
+ * CLIs alike.
+ */
+
+// Alias of the table whose rows are serialized
+const ROW_ALIAS: &str = "dynamic_row";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableColumn {
+    pub name: String,
+    // Type as declared in the database e.g. "character varying" or "tinyint(1)"
+    pub sql_type: String,
+    pub nullable: bool,
+    // Default expression as declared in the database e.g. "'Mr.'::character varying" or "nextval(...)"
+    pub default_value: Option<String>,
+    // Maximum number of characters of text columns
+    pub max_length: Option<i64>,
+    pub primary_key: bool,
+}
+
+// Cells of each row are ordered like the columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicTable {
+    pub columns: Vec<TableColumn>,
+    pub rows: Vec<Vec<ColumnValue>>,
+}
+
+// Column of a legacy table which provides the values of a field of the member
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    pub legacy_column: String,
+    pub member_field: String,
+}
+
+#[derive(QueryableByName, Deserialize, JsonFieldConversionGenerator)]
+struct ColumnRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    column_name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    data_type: String,
+    // "YES" or "NO" like in the information schema
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    is_nullable: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    column_default: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    character_maximum_length: Option<i64>,
+    // "YES" or "NO" since CLIs print booleans differently
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    is_primary_key: String,
+}
+
+#[derive(QueryableByName, Deserialize, JsonFieldConversionGenerator)]
+struct JsonRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    value: String,
+}
+
+const INTEGER_TYPES: [&str; 9] = [
+    "integer",
+    "int",
+    "int2",
+    "int4",
+    "int8",
+    "bigint",
+    "smallint",
+    "mediumint",
+    "tinyint",
+];
+
+// Unknown types (e.g. of extensions) are read as text
+fn parse_field_type(sql_type: &str) -> FieldType {
+    let lowercase_type = sql_type.trim().to_lowercase();
+
+    // Name of the type without length and attributes e.g. "bigint" of "bigint(20) unsigned"
+    let base_type = lowercase_type
+        .split(|c: char| c == '(' || c.is_whitespace())
+        .next()
+        .unwrap_or_default();
+
+    // MySQL represents booleans as tinyint(1)
+    if lowercase_type.starts_with("bool") || lowercase_type.starts_with("tinyint(1)") {
+        FieldType::Boolean
+    } else if INTEGER_TYPES.contains(&base_type) {
+        FieldType::Integer
+    } else if lowercase_type.starts_with("timestamp") || lowercase_type.starts_with("datetime") {
+        FieldType::DateTime
+    } else if lowercase_type.starts_with("date") {
+        FieldType::Date
+    } else if ["double", "real", "float", "numeric", "decimal"]
+        .iter()
+        .any(|numeric_type| lowercase_type.starts_with(numeric_type))
+    {
+        FieldType::Double
+    } else {
+        FieldType::Text
+    }
+}
+
+// SQLite does not enforce lengths but keeps the declared type e.g. "varchar(15)"
+fn parse_declared_length(sql_type: &str) -> Option<i64> {
+    if parse_field_type(sql_type) != FieldType::Text {
+        return None;
+    }
+
+    let opt_start = sql_type.find('(');
+    let opt_end = sql_type.rfind(')');
+
+    if opt_start.is_none() || opt_end.is_none() || opt_end.unwrap() < opt_start.unwrap() {
+        return None;
+    }
+
+    sql_type[opt_start.unwrap() + 1..opt_end.unwrap()]
+        .trim()
+        .parse::<i64>()
+        .ok()
+}
+
+fn get_type_info(column: &TableColumn) -> FieldTypeInfo {
+    FieldTypeInfo {
+        field_type: parse_field_type(&column.sql_type),
+        nullable: column.nullable,
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn create_information_schema_query(
+    table_name: &str,
+    type_column: &str,
+    current_schema: &'static str,
+    integer_type: &str,
+) -> SelectStatement {
+    Query::select()
+        .expr_as(Expr::col("column_name"), "column_name")
+        .expr_as(Expr::col(type_column.to_owned()), "data_type")
+        .expr_as(Expr::col("is_nullable"), "is_nullable")
+        .expr_as(Expr::col("column_default"), "column_default")
+        // The length is unsigned in MySQL and a domain type in PostgreSQL
+        .expr_as(
+            Expr::cust(format!(
+                "CAST(character_maximum_length AS {})",
+                integer_type
+            )),
+            "character_maximum_length",
+        )
+        .expr_as(
+            Expr::cust(
+                "CASE WHEN EXISTS (SELECT 1 FROM information_schema.table_constraints AS tc \
+                JOIN information_schema.key_column_usage AS kcu \
+                ON kcu.constraint_name = tc.constraint_name AND kcu.table_schema = tc.table_schema \
+                AND kcu.table_name = tc.table_name \
+                WHERE tc.constraint_type = 'PRIMARY KEY' AND kcu.table_schema = columns.table_schema \
+                AND kcu.table_name = columns.table_name AND kcu.column_name = columns.column_name) \
+                THEN 'YES' ELSE 'NO' END",
+            ),
+            "is_primary_key",
+        )
+        .from(("information_schema", "columns"))
+        .and_where(Expr::col("table_schema").eq(Expr::cust(current_schema)))
+        .and_where(Expr::col("table_name").eq(table_name))
+        .order_by("ordinal_position", Order::Asc)
+        .to_owned()
+}
+
+fn create_columns_query(backend: &DatabaseBackend, table_name: &str) -> SelectStatement {
+    return match backend {
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => {
+            create_information_schema_query(table_name, "data_type", "current_schema()", "BIGINT")
+        }
+        // In contrast to "data_type" the column type tells booleans and other tinyints apart
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => {
+            create_information_schema_query(table_name, "column_type", "DATABASE()", "SIGNED")
+        }
+        // SQLite has no information schema
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => Query::select()
+            .expr_as(Expr::col("name"), "column_name")
+            .expr_as(Expr::col("type"), "data_type")
+            .expr_as(
+                Expr::cust("CASE WHEN \"notnull\" = 0 THEN 'YES' ELSE 'NO' END"),
+                "is_nullable",
+            )
+            .expr_as(Expr::col("dflt_value"), "column_default")
+            .expr_as(Expr::cust("NULL"), "character_maximum_length")
+            .expr_as(
+                Expr::cust("CASE WHEN pk > 0 THEN 'YES' ELSE 'NO' END"),
+                "is_primary_key",
+            )
+            .from_function(
+                Func::cust("pragma_table_info").arg(table_name),
+                "table_info",
+            )
+            .order_by("cid", Order::Asc)
+            .to_owned(),
+    };
+}
+
+// Alternating names and values of all columns
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+fn create_object_arguments(columns: &[TableColumn]) -> Vec<Expr> {
+    columns
+        .iter()
+        .flat_map(|column| {
+            [
+                Expr::val(column.name.clone()),
+                Expr::col(column.name.clone()),
+            ]
+        })
+        .collect()
+}
+
+fn create_rows_query(
+    backend: &DatabaseBackend,
+    table_name: &str,
+    columns: &[TableColumn],
+) -> SelectStatement {
+    // NOTE 2026-10-18: The number of arguments of json_object() is limited to 127 by default in SQLite
+    let row_expression: Expr = match backend {
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => {
+            Expr::cust(format!("CAST(row_to_json({}) AS TEXT)", ROW_ALIAS))
+        }
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => Func::cast_as(
+            Func::cust("JSON_OBJECT").args(create_object_arguments(columns)),
+            "CHAR",
+        )
+        .into(),
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => Func::cust("json_object")
+            .args(create_object_arguments(columns))
+            .into(),
+    };
+
+    // Only the given columns are serialized
+    let columns_query = Query::select()
+        .columns(columns.iter().map(|column| column.name.clone()))
+        .from(table_name.to_owned())
+        .to_owned();
+
+    Query::select()
+        .expr_as(row_expression, "value")
+        .from_subquery(columns_query, ROW_ALIAS)
+        .to_owned()
+}
+
+fn convert_row(columns: &[TableColumn], json_row: &str) -> Result<Vec<ColumnValue>, DatabaseError> {
+    let parse_result = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(json_row);
+
+    if parse_result.is_err() {
+        let message = format!(
+            "Could not parse row '{}' due '{}'",
+            json_row,
+            parse_result.err().unwrap()
+        );
+        error!("{}", message);
+        return Err(DatabaseError::TypeConversion { message });
+    }
+
+    let json_object = parse_result.unwrap();
+
+    columns
+        .iter()
+        .map(|column| {
+            let opt_cell = json_object
+                .get(&column.name)
+                .and_then(|value| from_database_json_value(value, &get_type_info(column)));
+
+            if opt_cell.is_none() {
+                let message = format!(
+                    "Value of column '{}' in row '{}' does not match its type '{}'",
+                    column.name, json_row, column.sql_type
+                );
+                error!("{}", message);
+                return Err(DatabaseError::TypeConversion { message });
+            }
+
+            Ok(opt_cell.unwrap())
+        })
+        .collect()
+}
+
+pub(crate) async fn has_table(
+    connection: &mut DbConnection,
+    table_name: &str,
+) -> Result<bool, DatabaseError> {
+    let select_statement = create_columns_query(&connection.get_backend(), table_name);
+
+    connection
+        .load::<ColumnRow, _>(select_statement)
+        .await
+        .map(|column_rows| !column_rows.is_empty())
+}
+
+pub(crate) async fn get_table_columns_impl(
+    connection: &mut DbConnection,
+    table_name: &str,
+) -> Result<Vec<TableColumn>, DatabaseError> {
+    let select_statement = create_columns_query(&connection.get_backend(), table_name);
+    let column_rows = connection.load::<ColumnRow, _>(select_statement).await?;
+
+    if column_rows.is_empty() {
+        let message = format!("Table '{}' does not exist or has no columns", table_name);
+        error!("{}", message);
+        return Err(DatabaseError::NotFound { message });
+    }
+
+    Ok(column_rows
+        .into_iter()
+        .map(|column_row| TableColumn {
+            max_length: column_row
+                .character_maximum_length
+                .or_else(|| parse_declared_length(&column_row.data_type)),
+            name: column_row.column_name,
+            sql_type: column_row.data_type,
+            nullable: column_row.is_nullable.eq_ignore_ascii_case("YES"),
+            default_value: column_row.column_default,
+            primary_key: column_row.is_primary_key.eq_ignore_ascii_case("YES"),
+        })
+        .collect())
+}
+
+// Metadata of the columns of a table as declared in the database e.g. to validate input before changing records
+pub fn get_table_columns(
+    connection: ConnectionDescription,
+    table_name: String,
+) -> Result<Vec<TableColumn>, DatabaseError> {
+    with_connection(connection, async move |connection| {
+        get_table_columns_impl(connection, &table_name).await
+    })
+}
+
+pub(crate) async fn load_table_impl(
+    connection: &mut DbConnection,
+    table_name: &str,
+) -> Result<DynamicTable, DatabaseError> {
+    let columns = get_table_columns_impl(connection, table_name).await?;
+    let select_statement = create_rows_query(&connection.get_backend(), table_name, &columns);
+    let json_rows = connection.load::<JsonRow, _>(select_statement).await?;
+
+    let rows = json_rows
+        .iter()
+        .map(|json_row| convert_row(&columns, &json_row.value))
+        .collect::<Result<Vec<Vec<ColumnValue>>, DatabaseError>>()?;
+
+    Ok(DynamicTable { columns, rows })
+}
+
+// Load all rows of an arbitrary table of the database
+pub fn load_table(
+    connection: ConnectionDescription,
+    table_name: String,
+) -> Result<DynamicTable, DatabaseError> {
+    with_connection(connection, async move |connection| {
+        load_table_impl(connection, &table_name).await
+    })
+}
+
+// Map the columns whose names equal the ones of member fields apart from their case
+#[frb(sync)]
+pub fn get_default_column_mapping(columns: Vec<TableColumn>) -> Vec<ColumnMapping> {
+    columns
+        .into_iter()
+        .filter_map(|column| {
+            let field_name = get_field_name(&column.name).to_lowercase();
+
+            models::Member::get_field_names()
+                .into_iter()
+                .find(|member_field| *member_field == field_name)
+                .map(|member_field| ColumnMapping {
+                    legacy_column: column.name,
+                    member_field: member_field.to_owned(),
+                })
+        })
+        .collect()
+}
+
+// Column index and type of the member field of each mapping
+fn resolve_mappings(
+    columns: &[TableColumn],
+    mappings: &[ColumnMapping],
+) -> Result<Vec<(usize, FieldTypeInfo)>, DatabaseError> {
+    let mut resolved_mappings = Vec::with_capacity(mappings.len());
+
+    for (index, mapping) in mappings.iter().enumerate() {
+        let opt_column_index = columns
+            .iter()
+            .position(|column| column.name == mapping.legacy_column);
+        let opt_type_info = models::Member::get_field_type(&mapping.member_field);
+
+        let opt_error_message = if opt_column_index.is_none() {
+            Some(format!("Table has no column '{}'", mapping.legacy_column))
+        } else if opt_type_info.is_none() {
+            Some(format!("Member has no field '{}'", mapping.member_field))
+        } else if mappings[..index]
+            .iter()
+            .any(|previous_mapping| previous_mapping.member_field == mapping.member_field)
+        {
+            Some(format!(
+                "Field '{}' of member is mapped more than once",
+                mapping.member_field
+            ))
+        } else {
+            None
+        };
+
+        if let Some(message) = opt_error_message {
+            error!("{}", message);
+            return Err(DatabaseError::InvalidInput { message });
+        }
+
+        resolved_mappings.push((opt_column_index.unwrap(), opt_type_info.unwrap()));
+    }
+
+    Ok(resolved_mappings)
+}
+
+// Member whose fields are empty, zero, false, the current date or NULL if nullable
+fn create_blank_member() -> Result<models::Member, DatabaseError> {
+    let mut member_properties = serde_json::Map::new();
+
+    for field_name in models::Member::get_field_names() {
+        let type_info = models::Member::get_field_type(field_name).unwrap();
+        let blank_value = if type_info.nullable {
+            serde_json::Value::Null
+        } else {
+            match type_info.field_type {
+                FieldType::Text => serde_json::Value::String(String::new()),
+                FieldType::Integer => serde_json::Value::from(0),
+                FieldType::Boolean => serde_json::Value::Bool(false),
+                FieldType::Double => serde_json::Value::from(0.0),
+                FieldType::Date => serde_json::Value::String(Local::now().date_naive().to_string()),
+                FieldType::DateTime => serde_json::Value::String(
+                    Local::now()
+                        .naive_local()
+                        .format("%Y-%m-%dT%H:%M:%S")
+                        .to_string(),
+                ),
+            }
+        };
+        member_properties.insert(field_name.to_owned(), blank_value);
+    }
+
+    let member_result = serde_json::from_value(serde_json::Value::Object(member_properties));
+
+    if member_result.is_err() {
+        let message = format!(
+            "Blank member does not match member model due '{}'",
+            member_result.err().unwrap()
+        );
+        error!("{}", message);
+        return Err(DatabaseError::TypeConversion { message });
+    }
+
+    Ok(member_result.unwrap())
+}
+
+// Fields which are not mapped keep the values of a blank member
+pub(crate) fn convert_to_members(
+    table: &DynamicTable,
+    mappings: &[ColumnMapping],
+) -> Result<Vec<models::Member>, DatabaseError> {
+    let resolved_mappings = resolve_mappings(&table.columns, mappings)?;
+
+    if !mappings
+        .iter()
+        .any(|mapping| mapping.member_field == "membership_id")
+    {
+        let message = String::from("No column is mapped to the membership ID");
+        error!("{}", message);
+        return Err(DatabaseError::InvalidInput { message });
+    }
+
+    let template_result = serde_json::to_value(create_blank_member()?);
+
+    if template_result.is_err() {
+        let message = format!(
+            "Could not serialize blank member due '{}'",
+            template_result.err().unwrap()
+        );
+        error!("{}", message);
+        return Err(DatabaseError::TypeConversion { message });
+    }
+
+    let template = template_result.unwrap();
+
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let mut member_properties = template.clone();
+
+            for (mapping, (column_index, type_info)) in mappings.iter().zip(&resolved_mappings) {
+                let cell = &row[*column_index];
+
+                if !matches_field_type(cell, type_info) {
+                    let message = format!(
+                        "Value {:?} of column '{}' does not match field '{}' of member",
+                        cell, mapping.legacy_column, mapping.member_field
+                    );
+                    error!("{}", message);
+                    return Err(DatabaseError::TypeConversion { message });
+                }
+
+                member_properties[&mapping.member_field] = to_json_value(cell);
+            }
+
+            serde_json::from_value::<models::Member>(member_properties).map_err(|error| {
+                let message = format!("Could not create member from row due '{}'", error);
+                error!("{}", message);
+                DatabaseError::TypeConversion { message }
+            })
+        })
+        .collect()
+}
+
+// Read the members of a legacy table whose columns are mapped to the fields of the member
+pub fn load_legacy_members(
+    connection: ConnectionDescription,
+    table_name: String,
+    mappings: Vec<ColumnMapping>,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    with_connection(connection, async move |connection| {
+        let table = load_table_impl(connection, &table_name).await?;
+        convert_to_members(&table, &mappings)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+
+    use sea_query::{ColumnDef, Table};
+    use speculoos::prelude::*;
+
+    use crate::test_database_common::{self, GetCurrentDBName};
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        test_database_common::setup_test(sqlx_pool).await
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    fn create_column(name: &str, sql_type: &str, nullable: bool) -> TableColumn {
+        TableColumn {
+            name: name.to_owned(),
+            sql_type: sql_type.to_owned(),
+            nullable,
+            default_value: None,
+            max_length: None,
+            primary_key: false,
+        }
+    }
+
+    fn create_mapping(legacy_column: &str, member_field: &str) -> ColumnMapping {
+        ColumnMapping {
+            legacy_column: legacy_column.to_owned(),
+            member_field: member_field.to_owned(),
+        }
+    }
+
+    fn create_legacy_columns() -> Vec<TableColumn> {
+        vec![
+            create_column("nr", "integer", false),
+            create_column("Vorname", "character varying", false),
+            create_column("aktiv", "tinyint(1)", false),
+            create_column("eintritt", "date", true),
+        ]
+    }
+
+    #[test]
+    fn test_parse_field_type() {
+        assert_that!(parse_field_type("tinyint(1)")).is_equal_to(FieldType::Boolean);
+        assert_that!(parse_field_type("BOOLEAN")).is_equal_to(FieldType::Boolean);
+        assert_that!(parse_field_type("bigint(20) unsigned")).is_equal_to(FieldType::Integer);
+        assert_that!(parse_field_type("varchar(255)")).is_equal_to(FieldType::Text);
+        assert_that!(parse_field_type("timestamp without time zone"))
+            .is_equal_to(FieldType::DateTime);
+        assert_that!(parse_field_type("date")).is_equal_to(FieldType::Date);
+        assert_that!(parse_field_type("double precision")).is_equal_to(FieldType::Double);
+        assert_that!(parse_field_type("interval")).is_equal_to(FieldType::Text);
+        assert_that!(parse_field_type("INTEGER")).is_equal_to(FieldType::Integer);
+        assert_that!(parse_field_type("int4")).is_equal_to(FieldType::Integer);
+        assert_that!(parse_field_type("point")).is_equal_to(FieldType::Text);
+        assert_that!(parse_field_type("multipoint")).is_equal_to(FieldType::Text);
+        assert_that!(parse_field_type("int4range")).is_equal_to(FieldType::Text);
+        assert_that!(parse_field_type("USER-DEFINED")).is_equal_to(FieldType::Text);
+    }
+
+    #[test]
+    fn test_parse_declared_length() {
+        assert_that!(parse_declared_length("varchar(15)")).is_equal_to(Some(15));
+        assert_that!(parse_declared_length("CHARACTER ( 2 )")).is_equal_to(Some(2));
+        assert_that!(parse_declared_length("text")).is_none();
+        assert_that!(parse_declared_length("int(11)")).is_none();
+        assert_that!(parse_declared_length("enum('a','b')")).is_none();
+    }
+
+    #[test]
+    fn test_convert_row() {
+        let columns = create_legacy_columns();
+        let expected_row = vec![
+            ColumnValue::Integer(7),
+            ColumnValue::Text(String::from("Jane")),
+            ColumnValue::Boolean(true),
+            ColumnValue::Date(NaiveDate::from_ymd_opt(1990, 5, 1).unwrap()),
+        ];
+
+        // Rows as serialized by PostgreSQL and by MySQL or SQLite
+        assert_that!(convert_row(
+            &columns,
+            r#"{"nr":7,"Vorname":"Jane","aktiv":true,"eintritt":"1990-05-01"}"#
+        ))
+        .is_equal_to(Ok(expected_row.clone()));
+        assert_that!(convert_row(
+            &columns,
+            r#"{"nr": 7, "Vorname": "Jane", "aktiv": 1, "eintritt": "1990-05-01 00:00:00"}"#
+        ))
+        .is_equal_to(Ok(expected_row));
+
+        assert_that!(
+            convert_row(
+                &columns,
+                r#"{"nr":"seven","Vorname":"Jane","aktiv":1,"eintritt":null}"#
+            )
+            .map_err(|error| error.code())
+        )
+        .is_equal_to(Err(String::from("type_conversion")));
+    }
+
+    #[test]
+    fn test_convert_row_with_timestamps() {
+        let columns = vec![create_column("geaendert", "timestamp with time zone", true)];
+        let expected_row = vec![ColumnValue::DateTime(
+            NaiveDate::from_ymd_opt(2026, 10, 18)
+                .unwrap()
+                .and_hms_milli_opt(10, 30, 0, 500)
+                .unwrap(),
+        )];
+
+        // Offsets are applied whereas timestamps without offset are taken as they are
+        for serialized_row in [
+            r#"{"geaendert":"2026-10-18T12:30:00.5+02:00"}"#,
+            r#"{"geaendert":"2026-10-18T12:30:00.5+02"}"#,
+            r#"{"geaendert":"2026-10-18T10:30:00.5Z"}"#,
+            r#"{"geaendert":"2026-10-18 12:30:00.500+02:00"}"#,
+            r#"{"geaendert":"2026-10-18 10:30:00.500000"}"#,
+        ] {
+            assert_that!(convert_row(&columns, serialized_row))
+                .is_equal_to(Ok(expected_row.clone()));
+        }
+    }
+
+    #[test]
+    fn test_get_default_column_mapping() {
+        let mut columns = create_legacy_columns();
+        columns.push(create_column("Surname", "text", false));
+
+        assert_that!(get_default_column_mapping(columns))
+            .is_equal_to(vec![create_mapping("Surname", "surname")]);
+    }
+
+    #[test]
+    fn test_create_blank_member() {
+        let member = create_blank_member().unwrap();
+
+        assert_that!(member.membership_id).is_equal_to(0);
+        assert_that!(member.title).is_none();
+        assert_that!(member.is_founding_member).is_false();
+        assert_that!(member.is_honorary_member).is_false();
+    }
+
+    #[test]
+    fn test_convert_to_members() {
+        let table = DynamicTable {
+            columns: create_legacy_columns(),
+            rows: vec![vec![
+                ColumnValue::Integer(7),
+                ColumnValue::Text(String::from("Jane")),
+                ColumnValue::Boolean(true),
+                ColumnValue::Null,
+            ]],
+        };
+        let mappings = vec![
+            create_mapping("nr", "membership_id"),
+            create_mapping("Vorname", "prename"),
+            create_mapping("aktiv", "is_active"),
+        ];
+
+        let members = convert_to_members(&table, &mappings).unwrap();
+        assert_that!(members.len()).is_equal_to(1);
+        assert_that!(members[0].membership_id).is_equal_to(7);
+        assert_that!(members[0].prename).is_equal_to(String::from("Jane"));
+        assert_that!(members[0].is_active).is_true();
+        assert_that!(members[0].surname).is_equal_to(String::new());
+
+        let invalid_mappings = [
+            vec![create_mapping("Vorname", "prename")],
+            vec![create_mapping("nachname", "surname")],
+            vec![create_mapping("Vorname", "first_name")],
+            vec![
+                create_mapping("nr", "membership_id"),
+                create_mapping("nr", "membership_id"),
+            ],
+        ];
+        for invalid_mapping in invalid_mappings {
+            assert_that!(
+                convert_to_members(&table, &invalid_mapping)
+                    .map(|members| members.len())
+                    .map_err(|error| error.code())
+            )
+            .is_equal_to(Err(String::from("invalid_input")));
+        }
+
+        // Text does not fit into a boolean field
+        assert_that!(
+            convert_to_members(
+                &table,
+                &[
+                    create_mapping("nr", "membership_id"),
+                    create_mapping("Vorname", "is_active")
+                ]
+            )
+            .map(|members| members.len())
+            .map_err(|error| error.code())
+        )
+        .is_equal_to(Err(String::from("type_conversion")));
+    }
+
+    async fn test_load_legacy_table(mut connection: DbConnection) -> sqlx::Result<()> {
+        let table_creation_statement = Table::create()
+            .table("mitglied")
+            .col(ColumnDef::new("nr").integer().not_null().primary_key())
+            .col(ColumnDef::new("vorname").string().not_null())
+            .col(ColumnDef::new("aktiv").boolean().not_null())
+            .col(ColumnDef::new("eintritt").date().null())
+            .col(
+                ColumnDef::new("geaendert")
+                    .timestamp_with_time_zone()
+                    .null(),
+            )
+            .take();
+        assert_that!(connection.execute_sql(table_creation_statement).await).is_ok();
+
+        let insert_statement = Query::insert()
+            .into_table("mitglied")
+            .columns(["nr", "vorname", "aktiv", "eintritt", "geaendert"])
+            .values_panic([
+                7.into(),
+                "Jane".into(),
+                true.into(),
+                NaiveDate::from_ymd_opt(1990, 5, 1).into(),
+                "2026-10-18 12:30:00+02:00".into(),
+            ])
+            .to_owned();
+        assert_that!(connection.execute_sql(insert_statement).await).is_equal_to(Ok(1));
+
+        let table = load_table_impl(&mut connection, "mitglied").await.unwrap();
+        let column_names: Vec<&str> = table
+            .columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect();
+        assert_that!(column_names).is_equal_to(vec![
+            "nr",
+            "vorname",
+            "aktiv",
+            "eintritt",
+            "geaendert",
+        ]);
+        assert_that!(table.columns[3].nullable).is_true();
+        // NOTE 2026-10-18: MySQL stores timestamps in UTC and prints them in the time zone of the session (UTC in tests)
+        assert_that!(table.rows).is_equal_to(vec![vec![
+            ColumnValue::Integer(7),
+            ColumnValue::Text(String::from("Jane")),
+            ColumnValue::Boolean(true),
+            ColumnValue::Date(NaiveDate::from_ymd_opt(1990, 5, 1).unwrap()),
+            ColumnValue::DateTime(
+                NaiveDate::from_ymd_opt(2026, 10, 18)
+                    .unwrap()
+                    .and_hms_opt(10, 30, 0)
+                    .unwrap(),
+            ),
+        ]]);
+
+        let members = convert_to_members(
+            &table,
+            &[
+                create_mapping("nr", "membership_id"),
+                create_mapping("vorname", "prename"),
+                create_mapping("aktiv", "is_active"),
+            ],
+        )
+        .unwrap();
+        assert_that!(members[0].membership_id).is_equal_to(7);
+        assert_that!(members[0].prename).is_equal_to(String::from("Jane"));
+
+        let missing_table_result = get_table_columns_impl(&mut connection, "mitglieder").await;
+        assert_that!(missing_table_result.map_err(|error| error.code()))
+            .is_equal_to(Err(String::from("not_found")));
+
+        tear_down(1);
+        Ok(())
+    }
+
+    async fn test_get_table_columns(mut connection: DbConnection) -> sqlx::Result<()> {
+        test_database_common::create_member_tables(&mut connection).await;
+
+        let columns = get_table_columns_impl(&mut connection, "member")
+            .await
+            .unwrap();
+        let find_column = |name: &str| {
+            columns
+                .iter()
+                .find(|column| column.name == name)
+                .unwrap()
+                .clone()
+        };
+
+        let membership_id = find_column("membership_id");
+        assert_that!(membership_id.primary_key).is_true();
+        assert_that!(membership_id.nullable).is_false();
+        assert_that!(membership_id.max_length).is_none();
+
+        let title = find_column("title");
+        assert_that!(title.primary_key).is_false();
+        assert_that!(title.nullable).is_true();
+        assert_that!(title.max_length).is_equal_to(Some(15));
+
+        assert_that!(find_column("prename").max_length).is_equal_to(Some(255));
+        assert_that!(find_column("is_founding_member").default_value).is_some();
+        assert_that!(find_column("is_active").default_value).is_none();
+
+        tear_down(0);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_get_table_columns_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_get_table_columns(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_get_table_columns_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_get_table_columns(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_get_table_columns_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_get_table_columns(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_load_legacy_table_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_load_legacy_table(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_load_legacy_table_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_load_legacy_table(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_load_legacy_table_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_load_legacy_table(setup_test(pool).await).await
+    }
+}
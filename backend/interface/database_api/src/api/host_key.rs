@@ -0,0 +1,317 @@
+use crate::api::connection_manager::RUNTIME;
+use crate::connection::{probe_host_key, setup_ssh_hop};
+use backend_paths::paths::get_user_config_dir;
+use database_types::connection_description::{SshHop, SshTunnelDescription, TimeoutOptions};
+use log::{error, info};
+use russh::keys::known_hosts::{known_host_keys_path, learn_known_hosts_path};
+use russh::keys::{HashAlg, PublicKey, check_known_hosts_path};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostKeyStatus {
+    Trusted,
+    // The fingerprint has to be confirmed by the user before connecting
+    Unknown {
+        fingerprint: String,
+    },
+    // The connection may be intercepted. Known keys are never replaced automatically.
+    Mismatch {
+        fingerprint: String,
+        known_fingerprint: String,
+        known_hosts_line: usize,
+    },
+    Unverifiable,
+}
+
+// OpenSSH compatible file. Tests redirect it such that they do not modify the file of the user.
+static KNOWN_HOSTS_PATH: LazyLock<RwLock<PathBuf>> =
+    LazyLock::new(|| RwLock::new(get_user_config_dir().join("known_hosts")));
+
+pub(crate) fn get_known_hosts_path() -> PathBuf {
+    KNOWN_HOSTS_PATH.read().unwrap().clone()
+}
+
+#[cfg(test)]
+pub(crate) fn set_known_hosts_path(known_hosts_path: PathBuf) {
+    *KNOWN_HOSTS_PATH.write().unwrap() = known_hosts_path;
+}
+
+pub(crate) fn get_fingerprint(public_key: &PublicKey) -> String {
+    public_key.fingerprint(HashAlg::Sha256).to_string()
+}
+
+// Fingerprint of the key stored in the given line of the known hosts
+fn get_known_fingerprint(known_hosts_path: &Path, host: &str, port: u16, line: usize) -> String {
+    return match known_host_keys_path(host, port, known_hosts_path) {
+        Ok(known_keys) => known_keys
+            .iter()
+            .find(|(known_line, _)| *known_line == line)
+            .map(|(_, known_key)| get_fingerprint(known_key))
+            .unwrap_or_default(),
+        Err(error) => {
+            error!("Could not read known host keys due '{}'", error);
+            String::new()
+        }
+    };
+}
+
+pub(crate) fn verify_host_key(
+    known_hosts_path: &Path,
+    host: &str,
+    port: u16,
+    public_key: &PublicKey,
+) -> HostKeyStatus {
+    let fingerprint = get_fingerprint(public_key);
+
+    return match check_known_hosts_path(host, port, public_key, known_hosts_path) {
+        Ok(true) => HostKeyStatus::Trusted,
+        Ok(false) => {
+            info!(
+                "Host key '{}' of '{}:{}' is unknown",
+                fingerprint, host, port
+            );
+            HostKeyStatus::Unknown { fingerprint }
+        }
+        Err(russh::keys::Error::KeyChanged { line }) => {
+            error!(
+                "Host key '{}' of '{}:{}' does not match the known key in line {} of '{}'. The connection may be \
+                 intercepted.",
+                fingerprint,
+                host,
+                port,
+                line,
+                known_hosts_path.display()
+            );
+            HostKeyStatus::Mismatch {
+                fingerprint,
+                known_fingerprint: get_known_fingerprint(known_hosts_path, host, port, line),
+                known_hosts_line: line,
+            }
+        }
+        Err(error) => {
+            error!(
+                "Could not verify host key of '{}:{}' due '{}'",
+                host, port, error
+            );
+            HostKeyStatus::Unverifiable
+        }
+    };
+}
+
+/* Hops behind the first one are only reachable through the previous ones. Therefore the hops are verified one after
+ * another. Return the status of the first hop whose host key is not trusted together with the hop and its key.
+ */
+async fn probe_hops(
+    ssh_tunnel: &SshTunnelDescription,
+) -> (HostKeyStatus, Option<(SshHop, PublicKey)>) {
+    let hops = ssh_tunnel.get_hops();
+    // Tunnels are verified independently of the connections using them
+    let timeouts = TimeoutOptions::default();
+    let mut opt_jump_session = None;
+
+    for (hop_index, hop) in hops.iter().enumerate() {
+        let opt_observation =
+            probe_host_key(&hop.host, hop.port, opt_jump_session.clone(), &timeouts).await;
+
+        if opt_observation.is_none() {
+            return (HostKeyStatus::Unverifiable, None);
+        }
+
+        let (host_key_status, public_key) = opt_observation.unwrap();
+
+        if host_key_status != HostKeyStatus::Trusted {
+            return (host_key_status, Some((hop.clone(), public_key)));
+        }
+
+        if hop_index + 1 < hops.len() {
+            let hop_session_result = setup_ssh_hop(hop, opt_jump_session, &timeouts).await;
+
+            if hop_session_result.is_err() {
+                return (HostKeyStatus::Unverifiable, None);
+            }
+
+            opt_jump_session = Some(Arc::new(hop_session_result.unwrap()));
+        }
+    }
+
+    (HostKeyStatus::Trusted, None)
+}
+
+pub(crate) async fn get_host_key_status_impl(ssh_tunnel: &SshTunnelDescription) -> HostKeyStatus {
+    probe_hops(ssh_tunnel).await.0
+}
+
+// Status of the first hop of the tunnel whose host key is not trusted
+pub fn get_host_key_status(ssh_tunnel: SshTunnelDescription) -> HostKeyStatus {
+    RUNTIME.block_on(get_host_key_status_impl(&ssh_tunnel))
+}
+
+// Trust the key only if it still matches the fingerprint the user confirmed
+fn learn_host_key(
+    known_hosts_path: &Path,
+    host: &str,
+    port: u16,
+    public_key: &PublicKey,
+    confirmed_fingerprint: &str,
+) -> bool {
+    let fingerprint = get_fingerprint(public_key);
+
+    if fingerprint != confirmed_fingerprint {
+        error!(
+            "Host key of '{}:{}' changed since its fingerprint was confirmed",
+            host, port
+        );
+        return false;
+    }
+
+    return match learn_known_hosts_path(host, port, public_key, known_hosts_path) {
+        Ok(_) => {
+            info!("Accepted host key '{}' of '{}:{}'", fingerprint, host, port);
+            true
+        }
+        Err(error) => {
+            error!("Could not store host key due '{}'", error);
+            false
+        }
+    };
+}
+
+pub(crate) async fn accept_host_key_impl(
+    ssh_tunnel: &SshTunnelDescription,
+    confirmed_fingerprint: &str,
+) -> bool {
+    let (host_key_status, opt_untrusted_hop) = probe_hops(ssh_tunnel).await;
+
+    return match host_key_status {
+        HostKeyStatus::Trusted => true,
+        HostKeyStatus::Unknown { .. } => {
+            let (hop, public_key) = opt_untrusted_hop.unwrap();
+            learn_host_key(
+                &get_known_hosts_path(),
+                &hop.host,
+                hop.port,
+                &public_key,
+                confirmed_fingerprint,
+            )
+        }
+        HostKeyStatus::Mismatch { .. } | HostKeyStatus::Unverifiable => {
+            error!(
+                "Refusing to accept host key of tunnel to '{}:{}'",
+                ssh_tunnel.host, ssh_tunnel.port
+            );
+            false
+        }
+    };
+}
+
+/* Trust the first untrusted hop of the tunnel if it still presents the key of the fingerprint the user confirmed.
+ * Tunnels with several unknown hops require accepting each of them.
+ */
+pub fn accept_host_key(ssh_tunnel: SshTunnelDescription, confirmed_fingerprint: String) -> bool {
+    RUNTIME.block_on(accept_host_key_impl(&ssh_tunnel, &confirmed_fingerprint))
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const HOST: &str = "db.example.org";
+    const PORT: u16 = 2222;
+
+    fn setup_test(test_name: &str) -> PathBuf {
+        backend_testing::testing::setup_test();
+
+        let known_hosts_path =
+            std::env::temp_dir().join(format!("known_hosts_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_file(&known_hosts_path);
+        known_hosts_path
+    }
+
+    fn tear_down(known_hosts_path: &Path, expected_num_severe_messages: usize) {
+        let _ = std::fs::remove_file(known_hosts_path);
+        backend_testing::testing::tear_down(expected_num_severe_messages);
+    }
+
+    fn create_public_key(openssh_key: &str) -> PublicKey {
+        PublicKey::from_openssh(openssh_key).unwrap()
+    }
+
+    fn create_known_key() -> PublicKey {
+        create_public_key(
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIJ3e6TzBwlGubCUqYSQ/wlFLR2citfFaa6z0ZnGErxBj",
+        )
+    }
+
+    fn create_other_key() -> PublicKey {
+        create_public_key(
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIBClBqkPU0v2qg4hgkuacbsvkdkQ8optbgdlyH63SbjJ",
+        )
+    }
+
+    #[test]
+    fn test_verify_host_key() {
+        let known_hosts_path = setup_test("verify");
+        let known_key = create_known_key();
+        let other_key = create_other_key();
+
+        assert_that!(verify_host_key(&known_hosts_path, HOST, PORT, &known_key)).is_equal_to(
+            HostKeyStatus::Unknown {
+                fingerprint: get_fingerprint(&known_key),
+            },
+        );
+
+        let known_hosts_entry =
+            format!("[{}]:{} {}\n", HOST, PORT, known_key.to_openssh().unwrap());
+        assert_that!(std::fs::write(&known_hosts_path, known_hosts_entry)).is_ok();
+        assert_that!(verify_host_key(&known_hosts_path, HOST, PORT, &known_key))
+            .is_equal_to(HostKeyStatus::Trusted);
+        assert_that!(verify_host_key(&known_hosts_path, HOST, PORT, &other_key)).is_equal_to(
+            HostKeyStatus::Mismatch {
+                fingerprint: get_fingerprint(&other_key),
+                known_fingerprint: get_fingerprint(&known_key),
+                known_hosts_line: 1,
+            },
+        );
+
+        // The mismatching key
+        tear_down(&known_hosts_path, 1);
+    }
+
+    #[test]
+    fn test_learn_host_key() {
+        let known_hosts_path = setup_test("learn");
+        let known_key = create_known_key();
+        let other_fingerprint = get_fingerprint(&create_other_key());
+
+        assert_that!(learn_host_key(
+            &known_hosts_path,
+            HOST,
+            PORT,
+            &known_key,
+            &other_fingerprint
+        ))
+        .is_false();
+        assert_that!(verify_host_key(&known_hosts_path, HOST, PORT, &known_key)).is_equal_to(
+            HostKeyStatus::Unknown {
+                fingerprint: get_fingerprint(&known_key),
+            },
+        );
+
+        assert_that!(learn_host_key(
+            &known_hosts_path,
+            HOST,
+            PORT,
+            &known_key,
+            &get_fingerprint(&known_key)
+        ))
+        .is_true();
+        assert_that!(verify_host_key(&known_hosts_path, HOST, PORT, &known_key))
+            .is_equal_to(HostKeyStatus::Trusted);
+
+        // The fingerprint which does not match
+        tear_down(&known_hosts_path, 1);
+    }
+}
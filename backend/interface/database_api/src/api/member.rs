@@ -1,75 +1,573 @@
+use crate::api::connection_manager::with_connection;
+use crate::api::database_error::DatabaseError;
+use crate::api::member_audit::{create_audit_insert_statement, get_author};
 use crate::api::models;
-use crate::connection::get_connection;
-use crate::db_connection::DbConnection;
+use crate::db_connection::{DbConnection, TransactionOutcome};
+use crate::json_field_conversion::{FieldTypeInfo, JsonFieldConversion, get_field_name};
+use crate::sql_stringifier::SqlStringifier;
+use crate::value_conversion::{from_json_value, matches_field_type, to_expr};
+use database_types::column_value::ColumnValue;
 use database_types::connection_description::ConnectionDescription;
-use log::{error, info};
-use sea_query::Asterisk;
+use log::{error, info, warn};
 use sea_query::Expr;
+use sea_query::ExprTrait;
+use sea_query::InsertStatement;
+use sea_query::Order;
 use sea_query::Query;
-use tokio::runtime::Runtime;
+use sea_query::SelectStatement;
+use sea_query::UpdateStatement;
+use std::collections::{BTreeMap, HashSet};
 
-async fn get_all_members_impl(mut connection: DbConnection) -> Option<Vec<models::Member>> {
-    let query = Query::select().column(Asterisk).from("member").to_owned();
-    connection.load_member(query).await
+/* Select all columns under the names of the fields of the member model since the ORM looks up the values by the field
+ * names which differ from the column names containing non-ASCII characters (see get_field_name)
+ */
+pub(crate) fn create_members_query() -> SelectStatement {
+    let mut select_statement = Query::select();
+
+    for field_name in models::Member::get_field_names() {
+        select_statement.expr_as(
+            Expr::col(models::Member::get_column_name(field_name).unwrap()),
+            field_name,
+        );
+    }
+
+    select_statement.from("member").to_owned()
 }
 
-pub fn get_all_members(connection: ConnectionDescription) -> Option<Vec<models::Member>> {
-    return Runtime::new()
-        .unwrap()
-        .block_on(async { get_all_members_impl(get_connection(connection).await?).await });
+// Members who exited the club are not part of the default queries
+pub(crate) fn create_current_members_query() -> SelectStatement {
+    create_members_query()
+        .and_where(
+            Expr::col("membership_id").not_in_subquery(
+                Query::select()
+                    .column("membership_id")
+                    .from("member_exit")
+                    .to_owned(),
+            ),
+        )
+        .to_owned()
+}
+
+pub(crate) async fn get_all_members_impl(
+    connection: &mut DbConnection,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    connection
+        .load::<models::Member, _>(create_current_members_query())
+        .await
+}
+
+pub fn get_all_members(
+    connection: ConnectionDescription,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    with_connection(connection, async |connection| {
+        get_all_members_impl(connection).await
+    })
 }
 
+#[derive(Clone)]
 pub struct ChangeRecord {
     // Primary key for identification
     pub membershipid: i32,
 
     // Data to change
     pub column: String,
-    // FIXME How to transport type information for value or even derive it from column?
-    pub previous_value: Option<String>,
-    pub new_value: Option<String>,
+    pub previous_value: ColumnValue,
+    pub new_value: ColumnValue,
+}
+
+// Describes a change whose previous value does not match the value currently stored in the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeConflict {
+    pub membershipid: i32,
+    pub column: String,
+    pub expected_value: ColumnValue,
+    pub actual_value: ColumnValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeStatus {
+    Applied,
+    MemberNotFound,
+    UnknownColumn,
+    TypeMismatch,
+    Conflict(ChangeConflict),
+    // Not applied since another change of the same commit could not be applied
+    Skipped,
+    Failed,
+}
+
+pub struct ChangeRecordResult {
+    pub record: ChangeRecord,
+    pub status: ChangeStatus,
+}
+
+pub struct RollbackCause {
+    pub membershipid: i32,
+    pub statement: String,
+    // Absent if the statement succeeded without affecting any row
+    pub error: Option<DatabaseError>,
+}
+
+pub struct ChangeMemberResult {
+    pub record_results: Vec<ChangeRecordResult>,
+    pub rollback_cause: Option<RollbackCause>,
+}
+
+/* Indices of the change records grouped by the membership ID they refer to. Changes which keep the previous value are
+ * left out since the MySQL CLI reports changed instead of matched rows (hence their updates would roll back).
+ */
+fn group_changes_by_member(changes: &[ChangeRecord]) -> BTreeMap<i32, Vec<usize>> {
+    let mut grouped_changes = BTreeMap::<i32, Vec<usize>>::new();
+
+    for (index, change) in changes.iter().enumerate() {
+        if change.new_value == change.previous_value {
+            continue;
+        }
+
+        grouped_changes
+            .entry(change.membershipid)
+            .or_default()
+            .push(index);
+    }
+
+    grouped_changes
 }
 
-pub fn change_member(connection: ConnectionDescription, changes: Vec<ChangeRecord>) -> usize {
-    return Runtime::new().unwrap().block_on(async {
-        let opt_connection = get_connection(connection).await;
+pub(crate) fn get_column_type(column: &str) -> Option<FieldTypeInfo> {
+    models::Member::get_field_type(&get_field_name(column))
+}
 
-        if opt_connection.is_none() {
-            // FIXME Either throw exception or log warning etc.
-            error!("Could not establish connection");
-            return 0;
+// Check a change against the member model before any SQL is built. Return the reason if it is invalid.
+fn validate_change(change: &ChangeRecord) -> Option<ChangeStatus> {
+    return match get_column_type(&change.column) {
+        Some(type_info)
+            if matches_field_type(&change.previous_value, &type_info)
+                && matches_field_type(&change.new_value, &type_info) =>
+        {
+            None
+        }
+        Some(_) => {
+            warn!(
+                "Values of change of column '{}' do not match its type",
+                change.column
+            );
+            Some(ChangeStatus::TypeMismatch)
         }
+        None => {
+            warn!("Member has no column '{}'", change.column);
+            Some(ChangeStatus::UnknownColumn)
+        }
+    };
+}
+
+fn get_column_value(member: &models::Member, column: &str) -> Option<ColumnValue> {
+    let opt_type_info = get_column_type(column);
+
+    if opt_type_info.is_none() {
+        error!("Member has no column '{}'", column);
+        return None;
+    }
+
+    let serialized_member = serde_json::to_value(member);
+
+    if serialized_member.is_err() {
+        error!(
+            "Could not serialize member due '{}'",
+            serialized_member.err().unwrap()
+        );
+        return None;
+    }
+
+    let opt_value = serialized_member
+        .unwrap()
+        .get(get_field_name(column))
+        .and_then(|value| from_json_value(value, &opt_type_info.unwrap()));
+
+    if opt_value.is_none() {
+        error!("Could not determine value of column '{}'", column);
+    }
 
-        info!("Changing {} members...", changes.len());
+    opt_value
+}
 
-        let mut change_entries = Vec::<(_, Expr)>::new();
+pub(crate) async fn load_member_by_id(
+    connection: &mut DbConnection,
+    membershipid: i32,
+) -> Result<Option<models::Member>, DatabaseError> {
+    let query = create_members_query()
+        .and_where(Expr::col("membership_id").eq(membershipid))
+        .to_owned();
 
-        for change in changes.iter() {
-            change_entries.push((
-                change.column.clone(),
-                change
-                    .new_value
-                    .clone()
-                    .map_or(Expr::null(), |value| Expr::value(value)),
-            ));
+    return match connection.load::<models::Member, _>(query).await {
+        Ok(members) => Ok(members.into_iter().next()),
+        Err(error) => {
+            error!("Could not load member '{}'", membershipid);
+            Err(error)
         }
+    };
+}
+
+async fn determine_change_statuses(
+    connection: &mut DbConnection,
+    membershipid: i32,
+    changes: &[&ChangeRecord],
+) -> Vec<ChangeStatus> {
+    let stored_member_result = load_member_by_id(connection, membershipid).await;
+
+    if stored_member_result.is_err() {
+        return vec![ChangeStatus::Failed; changes.len()];
+    }
+
+    let stored_member = stored_member_result.unwrap();
+
+    if stored_member.is_none() {
+        warn!("Member '{}' does not exist", membershipid);
+        return vec![ChangeStatus::MemberNotFound; changes.len()];
+    }
+
+    let stored_member = stored_member.unwrap();
 
-        let update_statement = Query::update()
-            .table("member")
-            .values(change_entries)
-            .to_owned();
+    let statuses: Vec<ChangeStatus> = changes
+        .iter()
+        .map(
+            |change| match get_column_value(&stored_member, &change.column) {
+                Some(actual_value) if actual_value != change.previous_value => {
+                    ChangeStatus::Conflict(ChangeConflict {
+                        membershipid,
+                        column: change.column.clone(),
+                        expected_value: change.previous_value.clone(),
+                        actual_value,
+                    })
+                }
+                Some(_) => ChangeStatus::Skipped,
+                None => ChangeStatus::Failed,
+            },
+        )
+        .collect();
+
+    if !statuses
+        .iter()
+        .any(|status| matches!(status, ChangeStatus::Conflict(_)))
+    {
+        error!(
+            "Changing member '{}' had no effect although no conflict was found",
+            membershipid
+        );
+        return vec![ChangeStatus::Failed; changes.len()];
+    }
+
+    warn!("Changes of member '{}' conflict", membershipid);
+    statuses
+}
 
-        let mut connection = opt_connection.unwrap();
-        let num_updated_rows = connection.execute_sql(update_statement).await;
+fn create_update_statement(membershipid: i32, member_changes: &[&ChangeRecord]) -> UpdateStatement {
+    let change_entries: Vec<(String, Expr)> = member_changes
+        .iter()
+        .map(|change| (change.column.clone(), to_expr(&change.new_value)))
+        .collect();
+
+    let mut update_statement = Query::update()
+        .table("member")
+        .values(change_entries)
+        .and_where(Expr::col("membership_id").eq(membershipid))
+        .to_owned();
+
+    // Only apply changes if the stored values still match the values the changes are based on
+    for change in member_changes {
+        update_statement.and_where(match &change.previous_value {
+            ColumnValue::Null => Expr::col(change.column.clone()).is_null(),
+            value => Expr::col(change.column.clone()).eq(to_expr(value)),
+        });
+    }
+
+    update_statement
+}
+
+pub(crate) async fn change_member_impl(
+    connection: &mut DbConnection,
+    changes: Vec<ChangeRecord>,
+) -> Result<ChangeMemberResult, DatabaseError> {
+    let opt_invalid_statuses: Vec<Option<ChangeStatus>> =
+        changes.iter().map(validate_change).collect();
+
+    // Valid changes are skipped as well if any change is invalid
+    if opt_invalid_statuses.iter().any(Option::is_some) {
+        return Ok(ChangeMemberResult {
+            record_results: changes
+                .into_iter()
+                .zip(opt_invalid_statuses)
+                .map(|(record, opt_status)| ChangeRecordResult {
+                    record,
+                    status: opt_status.unwrap_or(ChangeStatus::Skipped),
+                })
+                .collect(),
+            rollback_cause: None,
+        });
+    }
+
+    let grouped_changes: Vec<(i32, Vec<usize>)> =
+        group_changes_by_member(&changes).into_iter().collect();
+
+    info!(
+        "Changing {} columns of {} members...",
+        changes.len(),
+        grouped_changes.len()
+    );
+
+    let update_statements: Vec<UpdateStatement> = grouped_changes
+        .iter()
+        .map(|(membershipid, change_indices)| {
+            let member_changes: Vec<&ChangeRecord> = change_indices
+                .iter()
+                .map(|&index| &changes[index])
+                .collect();
+            create_update_statement(*membershipid, &member_changes)
+        })
+        .collect();
+
+    // Every update is followed by the audit entries of its changes
+    let backend = connection.get_backend();
+    let author = get_author();
+    let mut statements: Vec<String> = vec![];
+    let mut update_statement_indices = Vec::with_capacity(grouped_changes.len());
+
+    for ((_, change_indices), update_statement) in grouped_changes.iter().zip(update_statements) {
+        update_statement_indices.push(statements.len());
+        statements.push(backend.to_sql_string(update_statement));
+
+        for &index in change_indices {
+            statements.push(
+                backend.to_sql_string(create_audit_insert_statement(&changes[index], &author)),
+            );
+        }
+    }
+
+    let mut statuses = vec![ChangeStatus::Skipped; changes.len()];
+    let mut rollback_cause = None;
+
+    let transaction_result = connection.execute_transaction(statements.clone()).await;
+
+    if transaction_result.is_err() {
+        error!("Changing members failed");
+        return Err(transaction_result.err().unwrap());
+    }
+
+    match transaction_result.unwrap() {
+        // Statements without effect roll back the transaction. Therefore committing implies all updates applied.
+        TransactionOutcome::Committed => {
+            statuses = vec![ChangeStatus::Applied; changes.len()];
+        }
+        TransactionOutcome::RolledBack {
+            statement_index,
+            statement_result,
+        } => {
+            let group_index = update_statement_indices.partition_point(|&update_statement_index| {
+                update_statement_index <= statement_index
+            }) - 1;
+            let (membershipid, change_indices) = &grouped_changes[group_index];
+            let member_changes: Vec<&ChangeRecord> = change_indices
+                .iter()
+                .map(|&index| &changes[index])
+                .collect();
+
+            let is_update_statement = update_statement_indices[group_index] == statement_index;
+            let member_statuses = match &statement_result {
+                Ok(0) if is_update_statement => {
+                    determine_change_statuses(connection, *membershipid, &member_changes).await
+                }
+                _ => vec![ChangeStatus::Failed; change_indices.len()],
+            };
+
+            for (&index, status) in change_indices.iter().zip(member_statuses) {
+                statuses[index] = status;
+            }
+
+            rollback_cause = Some(RollbackCause {
+                membershipid: *membershipid,
+                statement: statements[statement_index].clone(),
+                error: statement_result.err(),
+            });
+        }
+    }
+
+    Ok(ChangeMemberResult {
+        record_results: changes
+            .into_iter()
+            .zip(statuses)
+            .map(|(record, status)| ChangeRecordResult { record, status })
+            .collect(),
+        rollback_cause,
+    })
+}
+
+pub fn change_member(
+    connection: ConnectionDescription,
+    changes: Vec<ChangeRecord>,
+) -> Result<ChangeMemberResult, DatabaseError> {
+    with_connection(connection, async move |connection| {
+        change_member_impl(connection, changes).await
+    })
+}
+
+// Value of a column of a member to create
+#[derive(Clone)]
+pub struct MemberValue {
+    pub column: String,
+    pub value: ColumnValue,
+}
+
+// Check the values against the member model before any SQL is built
+fn validate_member_values(values: &[MemberValue]) -> Result<(), DatabaseError> {
+    let mut columns = HashSet::<&str>::new();
+
+    for member_value in values {
+        let opt_message = match get_column_type(&member_value.column) {
+            _ if member_value.column == "membership_id" => Some(String::from(
+                "Membership ID has to be passed separately from the other values",
+            )),
+            None => Some(format!("Member has no column '{}'", member_value.column)),
+            Some(type_info) if !matches_field_type(&member_value.value, &type_info) => {
+                Some(format!(
+                    "Value {:?} does not match column '{}'",
+                    member_value.value, member_value.column
+                ))
+            }
+            Some(_) if !columns.insert(&member_value.column) => Some(format!(
+                "Column '{}' has more than one value",
+                member_value.column
+            )),
+            Some(_) => None,
+        };
+
+        if let Some(message) = opt_message {
+            error!("{}", message);
+            return Err(DatabaseError::InvalidInput { message });
+        }
+    }
+
+    Ok(())
+}
+
+// Columns without value are left out so the database applies their defaults
+fn create_insert_statement(
+    membershipid: i32,
+    values: &[MemberValue],
+) -> Result<InsertStatement, DatabaseError> {
+    let mut columns = vec![String::from("membership_id")];
+    let mut exprs = vec![Expr::value(membershipid)];
+
+    for member_value in values {
+        columns.push(member_value.column.clone());
+        exprs.push(to_expr(&member_value.value));
+    }
+
+    let mut insert_statement = Query::insert()
+        .into_table("member")
+        .columns(columns)
+        .to_owned();
+
+    let values_result = insert_statement.values(exprs);
+
+    if values_result.is_err() {
+        let message = format!(
+            "Could not create insert statement due '{}'",
+            values_result.err().unwrap()
+        );
+        error!("{}", message);
+        return Err(DatabaseError::TypeConversion { message });
+    }
+
+    Ok(insert_statement)
+}
+
+async fn determine_next_membership_id(connection: &mut DbConnection) -> Result<i32, DatabaseError> {
+    let query = create_members_query()
+        .order_by("membership_id", Order::Desc)
+        .limit(1)
+        .to_owned();
+
+    return match connection.load::<models::Member, _>(query).await {
+        Ok(members) => Ok(members.first().map_or(1, |member| member.membership_id + 1)),
+        Err(error) => {
+            error!("Could not determine next membership ID");
+            Err(error)
+        }
+    };
+}
 
-        match num_updated_rows {
-            Some(num) => num,
-            None => {
-                error!("Updating member failed");
-                0
+async fn insert_new_member(
+    connection: &mut DbConnection,
+    opt_membershipid: Option<i32>,
+    values: &[MemberValue],
+) -> Result<models::Member, DatabaseError> {
+    let membershipid = match opt_membershipid {
+        Some(membershipid) => {
+            if load_member_by_id(connection, membershipid).await?.is_some() {
+                let message = format!("Membership ID '{}' is already in use", membershipid);
+                error!("{}", message);
+                return Err(DatabaseError::ConstraintViolation { message });
             }
+
+            membershipid
         }
-    });
+        None => determine_next_membership_id(connection).await?,
+    };
+
+    info!("Creating member '{}'...", membershipid);
+
+    let insert_statement = create_insert_statement(membershipid, values)?;
+    let num_inserted_rows = connection.execute_sql(insert_statement).await?;
+
+    if num_inserted_rows != 1 {
+        let message = format!(
+            "Inserting member '{}' affected {} rows",
+            membershipid, num_inserted_rows
+        );
+        error!("{}", message);
+        return Err(DatabaseError::UnexpectedResult { message });
+    }
+
+    return match load_member_by_id(connection, membershipid).await? {
+        Some(created_member) => Ok(created_member),
+        None => Err(DatabaseError::NotFound {
+            message: format!("Created member '{}' vanished", membershipid),
+        }),
+    };
+}
+
+/* The membership ID is determined, the member inserted and loaded within one transaction. If another member claims the
+ * same ID concurrently the primary key rejects the insert and the whole creation is rolled back.
+ */
+pub(crate) async fn create_member_impl(
+    connection: &mut DbConnection,
+    opt_membershipid: Option<i32>,
+    values: Vec<MemberValue>,
+) -> Result<models::Member, DatabaseError> {
+    validate_member_values(&values)?;
+
+    connection.begin_transaction().await?;
+
+    let insert_result = insert_new_member(connection, opt_membershipid, &values).await;
+
+    if insert_result.is_err() {
+        connection.rollback_transaction().await?;
+        return insert_result;
+    }
+
+    connection.commit_transaction().await?;
+
+    insert_result
+}
+
+// Without membership ID the next free one is assigned. Columns without value keep the default of the member table.
+pub fn create_member(
+    connection: ConnectionDescription,
+    opt_membershipid: Option<i32>,
+    values: Vec<MemberValue>,
+) -> Result<models::Member, DatabaseError> {
+    with_connection(connection, async move |connection| {
+        create_member_impl(connection, opt_membershipid, values).await
+    })
 }
 
 #[cfg(test)]
@@ -78,8 +576,12 @@ mod test {
     use sqlx::MySqlPool;
     #[cfg(feature = "postgres")]
     use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
     use sqlx::{Database, Pool};
 
+    use speculoos::prelude::*;
+
     use crate::test_database_common::{self, GetCurrentDBName};
 
     use super::*;
@@ -95,21 +597,340 @@ mod test {
         test_database_common::tear_down(expected_num_severe_messages);
     }
 
-    fn test_get_all(connection: DbConnection) -> sqlx::Result<()> {
-        let _ = get_all_members_impl(connection);
+    async fn test_get_all(mut connection: DbConnection) -> sqlx::Result<()> {
+        test_database_common::create_member_tables(&mut connection).await;
+
+        assert_that!(
+            get_all_members_impl(&mut connection)
+                .await
+                .map(|members| members.len())
+        )
+        .is_ok_containing(0);
+        tear_down(0);
+        Ok(())
+    }
+
+    async fn insert_member(connection: &mut DbConnection, membershipid: i32, surname: &str) {
+        let values = test_database_common::create_member_values(surname);
+
+        assert_that!(
+            create_member_impl(connection, Some(membershipid), values)
+                .await
+                .map(|member| member.membership_id)
+        )
+        .is_ok_containing(membershipid);
+    }
+
+    async fn test_create_member(mut connection: DbConnection) -> sqlx::Result<()> {
+        test_database_common::create_member_tables(&mut connection).await;
+        insert_member(&mut connection, 5, "Doe").await;
+
+        let member = create_member_impl(
+            &mut connection,
+            None,
+            test_database_common::create_member_values("Smith"),
+        )
+        .await
+        .unwrap();
+
+        assert_that!(member.membership_id).is_equal_to(6);
+        assert_that!(member.surname.as_str()).is_equal_to("Smith");
+        assert_that!(member.title).is_none();
+        assert_that!(member.is_founding_member).is_false();
+        assert_that!(member.is_honorary_member).is_false();
+
+        let duplicate_result = create_member_impl(
+            &mut connection,
+            Some(5),
+            test_database_common::create_member_values("Smith"),
+        )
+        .await;
+        assert_that!(matches!(
+            duplicate_result.err(),
+            Some(DatabaseError::ConstraintViolation { .. })
+        ))
+        .is_true();
+        assert_that!(load_surname(&mut connection, 5).await).is_equal_to(Some(String::from("Doe")));
+
+        // Missing values of columns without default are rejected by the database
+        assert_that!(
+            create_member_impl(&mut connection, None, vec![])
+                .await
+                .is_err()
+        )
+        .is_true();
+        assert_that!(
+            load_member_by_id(&mut connection, 7)
+                .await
+                .map(|member| member.is_none())
+        )
+        .is_ok_containing(true);
+
+        // The failed creations
+        tear_down(2);
+        Ok(())
+    }
+
+    async fn load_surname(connection: &mut DbConnection, membershipid: i32) -> Option<String> {
+        load_member_by_id(connection, membershipid)
+            .await
+            .unwrap()
+            .map(|member| member.surname)
+    }
+
+    async fn test_change_member(mut connection: DbConnection) -> sqlx::Result<()> {
+        test_database_common::create_member_tables(&mut connection).await;
+        insert_member(&mut connection, 1, "Doe").await;
+        insert_member(&mut connection, 2, "Doe").await;
+
+        let change = ChangeRecord {
+            membershipid: 2,
+            column: String::from("surname"),
+            previous_value: ColumnValue::Text(String::from("Doe")),
+            new_value: ColumnValue::Text(String::from("Smith")),
+        };
+        let result = change_member_impl(&mut connection, vec![change])
+            .await
+            .unwrap();
+
+        assert_that!(result.rollback_cause.is_none()).is_true();
+        assert_that!(result.record_results[0].status).is_equal_to(ChangeStatus::Applied);
+        assert_that!(load_surname(&mut connection, 1).await).is_equal_to(Some(String::from("Doe")));
+        assert_that!(load_surname(&mut connection, 2).await)
+            .is_equal_to(Some(String::from("Smith")));
+
         tear_down(0);
         Ok(())
     }
 
+    async fn test_change_member_conflict(mut connection: DbConnection) -> sqlx::Result<()> {
+        test_database_common::create_member_tables(&mut connection).await;
+        insert_member(&mut connection, 1, "Doe").await;
+
+        // Another client changes the member after the caller loaded it
+        assert_that!(
+            connection
+                .execute_sql(String::from(
+                    "UPDATE member SET surname = 'Miller' WHERE membership_id = 1"
+                ))
+                .await
+        )
+        .is_ok_containing(1);
+
+        let change = ChangeRecord {
+            membershipid: 1,
+            column: String::from("surname"),
+            previous_value: ColumnValue::Text(String::from("Doe")),
+            new_value: ColumnValue::Text(String::from("Smith")),
+        };
+        let result = change_member_impl(&mut connection, vec![change])
+            .await
+            .unwrap();
+
+        assert_that!(result.record_results[0].status).is_equal_to(ChangeStatus::Conflict(
+            ChangeConflict {
+                membershipid: 1,
+                column: String::from("surname"),
+                expected_value: ColumnValue::Text(String::from("Doe")),
+                actual_value: ColumnValue::Text(String::from("Miller")),
+            },
+        ));
+        let rollback_cause = result.rollback_cause.unwrap();
+        assert_that!(rollback_cause.membershipid).is_equal_to(1);
+        assert_that!(rollback_cause.error.is_none()).is_true();
+        assert_that!(load_surname(&mut connection, 1).await)
+            .is_equal_to(Some(String::from("Miller")));
+
+        // The rollback of the update without effect and the conflict
+        tear_down(2);
+        Ok(())
+    }
+
+    async fn test_change_member_rollback(mut connection: DbConnection) -> sqlx::Result<()> {
+        test_database_common::create_member_tables(&mut connection).await;
+        insert_member(&mut connection, 1, "Doe").await;
+        insert_member(&mut connection, 2, "Doe").await;
+
+        let changes = vec![
+            ChangeRecord {
+                membershipid: 1,
+                column: String::from("surname"),
+                previous_value: ColumnValue::Text(String::from("Doe")),
+                new_value: ColumnValue::Text(String::from("Smith")),
+            },
+            // Keeping the stored value must not roll back the transaction
+            ChangeRecord {
+                membershipid: 2,
+                column: String::from("surname"),
+                previous_value: ColumnValue::Text(String::from("Doe")),
+                new_value: ColumnValue::Text(String::from("Doe")),
+            },
+            ChangeRecord {
+                membershipid: 3,
+                column: String::from("surname"),
+                previous_value: ColumnValue::Text(String::from("Doe")),
+                new_value: ColumnValue::Text(String::from("Smith")),
+            },
+        ];
+        let result = change_member_impl(&mut connection, changes.clone())
+            .await
+            .unwrap();
+
+        assert_that!(result.record_results[0].status).is_equal_to(ChangeStatus::Skipped);
+        assert_that!(result.record_results[1].status).is_equal_to(ChangeStatus::Skipped);
+        assert_that!(result.record_results[2].status).is_equal_to(ChangeStatus::MemberNotFound);
+        assert_that!(result.rollback_cause.unwrap().membershipid).is_equal_to(3);
+        assert_that!(load_surname(&mut connection, 1).await).is_equal_to(Some(String::from("Doe")));
+        assert_that!(load_surname(&mut connection, 2).await).is_equal_to(Some(String::from("Doe")));
+
+        let result = change_member_impl(&mut connection, changes[..2].to_vec())
+            .await
+            .unwrap();
+
+        assert_that!(result.rollback_cause.is_none()).is_true();
+        assert_that!(result.record_results[1].status).is_equal_to(ChangeStatus::Applied);
+        assert_that!(load_surname(&mut connection, 1).await)
+            .is_equal_to(Some(String::from("Smith")));
+        assert_that!(load_surname(&mut connection, 2).await).is_equal_to(Some(String::from("Doe")));
+
+        // The rollback of the update without effect and the missing member
+        tear_down(2);
+        Ok(())
+    }
+
+    fn create_change_record(membershipid: i32, column: &str) -> ChangeRecord {
+        ChangeRecord {
+            membershipid,
+            column: column.to_owned(),
+            previous_value: ColumnValue::Null,
+            new_value: ColumnValue::Text(String::from("value")),
+        }
+    }
+
+    #[test]
+    fn test_group_changes_by_member() {
+        let changes = vec![
+            create_change_record(2, "surname"),
+            create_change_record(1, "prename"),
+            create_change_record(2, "city"),
+            ChangeRecord {
+                membershipid: 3,
+                column: String::from("city"),
+                previous_value: ColumnValue::Null,
+                new_value: ColumnValue::Null,
+            },
+        ];
+
+        let grouped_changes = group_changes_by_member(&changes);
+
+        assert_that!(grouped_changes.len()).is_equal_to(2);
+        assert_that!(grouped_changes[&1]).is_equal_to(vec![1]);
+        assert_that!(grouped_changes[&2]).is_equal_to(vec![0, 2]);
+    }
+
+    #[test]
+    fn test_validate_change() {
+        let mut unknown_column_change = create_change_record(1, "unknown_column");
+        unknown_column_change.new_value = ColumnValue::Null;
+        let mut type_mismatch_change = create_change_record(2, "is_male");
+        type_mismatch_change.previous_value = ColumnValue::Boolean(true);
+
+        assert_that!(validate_change(&create_change_record(1, "title"))).is_none();
+        assert_that!(validate_change(&unknown_column_change))
+            .is_some()
+            .is_equal_to(ChangeStatus::UnknownColumn);
+        assert_that!(validate_change(&type_mismatch_change))
+            .is_some()
+            .is_equal_to(ChangeStatus::TypeMismatch);
+    }
+
     #[cfg(feature = "postgres")]
     #[sqlx::test]
     async fn test_get_all_pg(pool: PgPool) -> sqlx::Result<()> {
-        test_get_all(setup_test(pool).await)
+        test_get_all(setup_test(pool).await).await
     }
 
     #[cfg(feature = "mysql")]
     #[sqlx::test]
     async fn test_get_all_mysql(pool: MySqlPool) -> sqlx::Result<()> {
-        test_get_all(setup_test(pool).await)
+        test_get_all(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_get_all_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_get_all(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_create_member_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_create_member(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_create_member_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_create_member(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_create_member_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_create_member(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_change_member_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_change_member(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_change_member_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_change_member(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_change_member_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_change_member(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_change_member_conflict_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_change_member_conflict(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_change_member_conflict_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_change_member_conflict(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_change_member_conflict_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_change_member_conflict(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_change_member_rollback_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_change_member_rollback(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_change_member_rollback_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_change_member_rollback(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_change_member_rollback_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_change_member_rollback(setup_test(pool).await).await
     }
 }
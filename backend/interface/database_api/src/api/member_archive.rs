@@ -0,0 +1,339 @@
+use crate::api::connection_manager::with_connection;
+use crate::api::database_error::DatabaseError;
+use crate::api::member::{create_current_members_query, create_members_query, load_member_by_id};
+use crate::api::member_audit::{create_history_delete_statement, get_member_history_impl};
+use crate::api::models;
+use crate::db_connection::{DbConnection, TransactionOutcome};
+use chrono::NaiveDate;
+use database_types::connection_description::ConnectionDescription;
+use log::{error, info, warn};
+use sea_query::Expr;
+use sea_query::ExprTrait;
+use sea_query::Query;
+
+async fn get_exited_members_impl(
+    connection: &mut DbConnection,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    let query = create_members_query()
+        .and_where(
+            Expr::col("membership_id").in_subquery(
+                Query::select()
+                    .column("membership_id")
+                    .from("member_exit")
+                    .to_owned(),
+            ),
+        )
+        .to_owned();
+
+    connection.load::<models::Member, _>(query).await
+}
+
+pub fn get_exited_members(
+    connection: ConnectionDescription,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    with_connection(connection, async |connection| {
+        get_exited_members_impl(connection).await
+    })
+}
+
+async fn is_current_member(
+    connection: &mut DbConnection,
+    membershipid: i32,
+) -> Result<bool, DatabaseError> {
+    let query = create_current_members_query()
+        .and_where(Expr::col("membership_id").eq(membershipid))
+        .to_owned();
+
+    connection
+        .load::<models::Member, _>(query)
+        .await
+        .map(|members| !members.is_empty())
+}
+
+async fn exit_member_impl(
+    connection: &mut DbConnection,
+    membershipid: i32,
+    exit_date: NaiveDate,
+    exit_reason: Option<String>,
+) -> Result<(), DatabaseError> {
+    if !is_current_member(connection, membershipid).await? {
+        let message = format!("Member '{}' does not exist or already exited", membershipid);
+        error!("{}", message);
+        return Err(DatabaseError::NotFound { message });
+    }
+
+    info!("Recording exit of member '{}'...", membershipid);
+
+    let insert_statement = Query::insert()
+        .into_table("member_exit")
+        .columns(["membership_id", "exit_date", "exit_reason"])
+        .values_panic([membershipid.into(), exit_date.into(), exit_reason.into()])
+        .to_owned();
+
+    let num_inserted_rows = connection.execute_sql(insert_statement).await?;
+
+    if num_inserted_rows != 1 {
+        return Err(DatabaseError::UnexpectedResult {
+            message: format!(
+                "Recording exit of member '{}' affected {} rows",
+                membershipid, num_inserted_rows
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn exit_member(
+    connection: ConnectionDescription,
+    membershipid: i32,
+    exit_date: NaiveDate,
+    exit_reason: Option<String>,
+) -> Result<(), DatabaseError> {
+    with_connection(connection, async move |connection| {
+        exit_member_impl(connection, membershipid, exit_date, exit_reason).await
+    })
+}
+
+// Summary of the member to purge which has to be confirmed by the user before purging
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurgeRequest {
+    pub membershipid: i32,
+    pub prename: String,
+    pub surname: String,
+}
+
+async fn request_member_purge_impl(
+    connection: &mut DbConnection,
+    membershipid: i32,
+) -> Result<PurgeRequest, DatabaseError> {
+    // Only members who already exited can be purged
+    if is_current_member(connection, membershipid).await? {
+        let message = format!("Member '{}' has to exit before being purged", membershipid);
+        warn!("{}", message);
+        return Err(DatabaseError::InvalidInput { message });
+    }
+
+    let opt_member = load_member_by_id(connection, membershipid).await?;
+
+    if opt_member.is_none() {
+        let message = format!("Member '{}' does not exist", membershipid);
+        warn!("{}", message);
+        return Err(DatabaseError::NotFound { message });
+    }
+
+    let member = opt_member.unwrap();
+
+    Ok(PurgeRequest {
+        membershipid: member.membership_id,
+        prename: member.prename,
+        surname: member.surname,
+    })
+}
+
+pub fn request_member_purge(
+    connection: ConnectionDescription,
+    membershipid: i32,
+) -> Result<PurgeRequest, DatabaseError> {
+    with_connection(connection, async |connection| {
+        request_member_purge_impl(connection, membershipid).await
+    })
+}
+
+async fn purge_member_impl(
+    connection: &mut DbConnection,
+    confirmed_request: PurgeRequest,
+) -> Result<(), DatabaseError> {
+    let current_request =
+        request_member_purge_impl(connection, confirmed_request.membershipid).await?;
+
+    // Refuse purging if the member changed since the request was confirmed
+    if current_request != confirmed_request {
+        let message = format!(
+            "Purge request of member '{}' is outdated",
+            confirmed_request.membershipid
+        );
+        error!("{}", message);
+        return Err(DatabaseError::InvalidInput { message });
+    }
+
+    info!("Purging member '{}'...", confirmed_request.membershipid);
+
+    let history_result = get_member_history_impl(connection, confirmed_request.membershipid).await;
+
+    if history_result.is_err() {
+        error!(
+            "Could not determine history of member '{}'",
+            confirmed_request.membershipid
+        );
+        return Err(history_result.err().unwrap());
+    }
+
+    let mut delete_statements = vec![];
+
+    // Statements without effect would roll back the transaction
+    if !history_result.unwrap().is_empty() {
+        delete_statements.push(create_history_delete_statement(
+            confirmed_request.membershipid,
+        ));
+    }
+
+    delete_statements.extend(["member_exit", "member"].into_iter().map(|table| {
+        Query::delete()
+            .from_table(table)
+            .and_where(Expr::col("membership_id").eq(confirmed_request.membershipid))
+            .to_owned()
+    }));
+
+    let transaction_result = connection.execute_transaction(delete_statements).await;
+
+    if !matches!(transaction_result, Ok(TransactionOutcome::Committed)) {
+        error!("Purging member '{}' failed", confirmed_request.membershipid);
+    }
+
+    return match transaction_result? {
+        TransactionOutcome::Committed => Ok(()),
+        TransactionOutcome::RolledBack {
+            statement_index,
+            statement_result,
+        } => Err(statement_result
+            .err()
+            .unwrap_or_else(|| DatabaseError::UnexpectedResult {
+                message: format!("Purging statement {} had no effect", statement_index),
+            })),
+    };
+}
+
+// Irreversibly delete all data of a member who exited (e.g. for GDPR requests)
+pub fn purge_member(
+    connection: ConnectionDescription,
+    confirmed_request: PurgeRequest,
+) -> Result<(), DatabaseError> {
+    with_connection(connection, async move |connection| {
+        purge_member_impl(connection, confirmed_request).await
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+
+    use speculoos::prelude::*;
+
+    use crate::api::member::{
+        ChangeRecord, ChangeStatus, change_member_impl, create_member_impl, get_all_members_impl,
+    };
+    use crate::test_database_common::{self, GetCurrentDBName};
+    use database_types::column_value::ColumnValue;
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        let mut connection = test_database_common::setup_test(sqlx_pool).await;
+        test_database_common::create_member_tables(&mut connection).await;
+        connection
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    async fn insert_member(connection: &mut DbConnection, membershipid: i32) {
+        let values = test_database_common::create_member_values("Doe");
+
+        assert_that!(
+            create_member_impl(connection, Some(membershipid), values)
+                .await
+                .map(|member| member.membership_id)
+        )
+        .is_ok_containing(membershipid);
+    }
+
+    async fn test_exit_and_purge(mut connection: DbConnection) -> sqlx::Result<()> {
+        insert_member(&mut connection, 1).await;
+        insert_member(&mut connection, 2).await;
+
+        let exit_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert_that!(
+            exit_member_impl(&mut connection, 1, exit_date, Some(String::from("Moved"))).await
+        )
+        .is_ok();
+
+        let current_members = get_all_members_impl(&mut connection).await.unwrap();
+        assert_that!(current_members.len()).is_equal_to(1);
+        assert_that!(current_members[0].membership_id).is_equal_to(2);
+
+        let exited_members = get_exited_members_impl(&mut connection).await.unwrap();
+        assert_that!(exited_members.len()).is_equal_to(1);
+        assert_that!(exited_members[0].membership_id).is_equal_to(1);
+
+        // The history of purged members is deleted as well
+        let change = ChangeRecord {
+            membershipid: 1,
+            column: String::from("surname"),
+            previous_value: ColumnValue::Text(String::from("Doe")),
+            new_value: ColumnValue::Text(String::from("Smith")),
+        };
+        let change_result = change_member_impl(&mut connection, vec![change])
+            .await
+            .unwrap();
+        assert_that!(change_result.record_results[0].status).is_equal_to(ChangeStatus::Applied);
+
+        let purge_request = request_member_purge_impl(&mut connection, 1).await.unwrap();
+        assert_that!(purge_request.surname.as_str()).is_equal_to("Smith");
+
+        // Confirmations of another member or outdated names are refused
+        let mut other_member_request = purge_request.clone();
+        other_member_request.membershipid = 2;
+        assert_that!(purge_member_impl(&mut connection, other_member_request).await).is_err();
+        let mut outdated_request = purge_request.clone();
+        outdated_request.surname = String::from("Doe");
+        assert_that!(purge_member_impl(&mut connection, outdated_request).await).is_err();
+        assert_that!(
+            load_member_by_id(&mut connection, 1)
+                .await
+                .map(|member| member.is_some())
+        )
+        .is_ok_containing(true);
+
+        assert_that!(purge_member_impl(&mut connection, purge_request).await).is_ok();
+
+        let purged_member = load_member_by_id(&mut connection, 1).await.unwrap();
+        assert_that!(purged_member.is_none()).is_true();
+        let exited_members = get_exited_members_impl(&mut connection).await.unwrap();
+        assert_that!(exited_members.len()).is_equal_to(0);
+        let history = get_member_history_impl(&mut connection, 1).await.unwrap();
+        assert_that!(history.len()).is_equal_to(0);
+
+        // The refused purges
+        tear_down(2);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_exit_and_purge_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_exit_and_purge(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_exit_and_purge_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_exit_and_purge(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_exit_and_purge_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_exit_and_purge(setup_test(pool).await).await
+    }
+}
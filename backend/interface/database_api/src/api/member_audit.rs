@@ -0,0 +1,308 @@
+use crate::api::connection_manager::with_connection;
+use crate::api::database_error::DatabaseError;
+use crate::api::member::{ChangeMemberResult, ChangeRecord, change_member_impl, get_column_type};
+use crate::api::models;
+use crate::db_connection::DbConnection;
+use crate::value_conversion::{from_text, to_expr, to_text};
+use chrono::NaiveDateTime;
+use database_types::column_value::ColumnValue;
+use database_types::connection_description::ConnectionDescription;
+use log::{error, info, warn};
+use sea_query::DeleteStatement;
+use sea_query::Expr;
+use sea_query::ExprTrait;
+use sea_query::InsertStatement;
+use sea_query::Order;
+use sea_query::Query;
+use sea_query::SelectStatement;
+
+pub struct AuditRecord {
+    pub change_id: i32,
+    pub changed_at: NaiveDateTime,
+    pub author: String,
+    pub change: ChangeRecord,
+}
+
+// The OS user is accountable for all changes since profiles are not bound to persons
+pub(crate) fn get_author() -> String {
+    let opt_author = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok();
+
+    if opt_author.is_none() {
+        warn!("Could not determine author of changes");
+        return String::from("unknown");
+    }
+
+    opt_author.unwrap()
+}
+
+pub(crate) fn create_audit_insert_statement(
+    change: &ChangeRecord,
+    author: &str,
+) -> InsertStatement {
+    // Only record changes which were actually applied since not all connections roll back statements without effect
+    let select_statement = Query::select()
+        .expr(Expr::current_timestamp())
+        .expr(Expr::value(author))
+        .expr(Expr::value(change.membershipid))
+        .expr(Expr::value(change.column.clone()))
+        .expr(Expr::value(to_text(&change.previous_value)))
+        .expr(Expr::value(to_text(&change.new_value)))
+        .from("member")
+        .and_where(Expr::col("membership_id").eq(change.membershipid))
+        .and_where(match &change.new_value {
+            ColumnValue::Null => Expr::col(change.column.clone()).is_null(),
+            value => Expr::col(change.column.clone()).eq(to_expr(value)),
+        })
+        .to_owned();
+
+    Query::insert()
+        .into_table("member_change")
+        .columns([
+            "changed_at",
+            "author",
+            "membership_id",
+            "column_name",
+            "previous_value",
+            "new_value",
+        ])
+        .select_from(select_statement)
+        .expect("Number of audit columns and values differ")
+        .to_owned()
+}
+
+pub(crate) fn create_history_delete_statement(membershipid: i32) -> DeleteStatement {
+    Query::delete()
+        .from_table("member_change")
+        .and_where(Expr::col("membership_id").eq(membershipid))
+        .to_owned()
+}
+
+fn create_history_query() -> SelectStatement {
+    Query::select()
+        .columns([
+            "change_id",
+            "changed_at",
+            "author",
+            "membership_id",
+            "column_name",
+            "previous_value",
+            "new_value",
+        ])
+        .from("member_change")
+        .to_owned()
+}
+
+fn to_audit_record(member_change: models::MemberChange) -> Result<AuditRecord, DatabaseError> {
+    let opt_type_info = get_column_type(&member_change.column_name);
+
+    if opt_type_info.is_none() {
+        let message = format!(
+            "Change {} refers to unknown column '{}'",
+            member_change.change_id, member_change.column_name
+        );
+        error!("{}", message);
+        return Err(DatabaseError::TypeConversion { message });
+    }
+
+    let type_info = opt_type_info.unwrap();
+    let opt_previous_value = from_text(member_change.previous_value.as_deref(), &type_info);
+    let opt_new_value = from_text(member_change.new_value.as_deref(), &type_info);
+
+    if opt_previous_value.is_none() || opt_new_value.is_none() {
+        let message = format!(
+            "Values of change {} do not match the type of column '{}'",
+            member_change.change_id, member_change.column_name
+        );
+        error!("{}", message);
+        return Err(DatabaseError::TypeConversion { message });
+    }
+
+    Ok(AuditRecord {
+        change_id: member_change.change_id,
+        changed_at: member_change.changed_at,
+        author: member_change.author,
+        change: ChangeRecord {
+            membershipid: member_change.membership_id,
+            column: member_change.column_name,
+            previous_value: opt_previous_value.unwrap(),
+            new_value: opt_new_value.unwrap(),
+        },
+    })
+}
+
+pub(crate) async fn get_member_history_impl(
+    connection: &mut DbConnection,
+    membershipid: i32,
+) -> Result<Vec<AuditRecord>, DatabaseError> {
+    let select_statement = create_history_query()
+        .and_where(Expr::col("membership_id").eq(membershipid))
+        .order_by("change_id", Order::Asc)
+        .to_owned();
+
+    let member_changes = connection
+        .load::<models::MemberChange, _>(select_statement)
+        .await?;
+    member_changes.into_iter().map(to_audit_record).collect()
+}
+
+// All changes of a member ordered from oldest to newest
+pub fn get_member_history(
+    connection: ConnectionDescription,
+    membershipid: i32,
+) -> Result<Vec<AuditRecord>, DatabaseError> {
+    with_connection(connection, async |connection| {
+        get_member_history_impl(connection, membershipid).await
+    })
+}
+
+async fn revert_member_change_impl(
+    connection: &mut DbConnection,
+    change_id: i32,
+) -> Result<ChangeMemberResult, DatabaseError> {
+    let select_statement = create_history_query()
+        .and_where(Expr::col("change_id").eq(change_id))
+        .to_owned();
+
+    let member_changes = connection
+        .load::<models::MemberChange, _>(select_statement)
+        .await?;
+    let opt_member_change = member_changes.into_iter().next();
+
+    if opt_member_change.is_none() {
+        let message = format!("There is no change {}", change_id);
+        error!("{}", message);
+        return Err(DatabaseError::NotFound { message });
+    }
+
+    let audit_record = to_audit_record(opt_member_change.unwrap())?;
+
+    info!("Reverting change {}...", change_id);
+
+    // Reverting fails if the value changed again in the meantime
+    let revert_change = ChangeRecord {
+        membershipid: audit_record.change.membershipid,
+        column: audit_record.change.column,
+        previous_value: audit_record.change.new_value,
+        new_value: audit_record.change.previous_value,
+    };
+
+    change_member_impl(connection, vec![revert_change]).await
+}
+
+pub fn revert_member_change(
+    connection: ConnectionDescription,
+    change_id: i32,
+) -> Result<ChangeMemberResult, DatabaseError> {
+    with_connection(connection, async |connection| {
+        revert_member_change_impl(connection, change_id).await
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+
+    use speculoos::prelude::*;
+
+    use crate::api::member::{ChangeStatus, create_member_impl, load_member_by_id};
+    use crate::test_database_common::{self, GetCurrentDBName};
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        let mut connection = test_database_common::setup_test(sqlx_pool).await;
+        test_database_common::create_member_tables(&mut connection).await;
+        connection
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    async fn test_history_and_revert(mut connection: DbConnection) -> sqlx::Result<()> {
+        let values = test_database_common::create_member_values("Doe");
+        assert_that!(
+            create_member_impl(&mut connection, Some(1), values)
+                .await
+                .map(|member| member.iban)
+        )
+        .is_ok_containing(String::from("DE02120300000000202051"));
+
+        let change = ChangeRecord {
+            membershipid: 1,
+            column: String::from("iban"),
+            previous_value: ColumnValue::Text(String::from("DE02120300000000202051")),
+            new_value: ColumnValue::Text(String::from("DE02500105170137075030")),
+        };
+        let change_result = change_member_impl(&mut connection, vec![change])
+            .await
+            .unwrap();
+        assert_that!(change_result.record_results[0].status).is_equal_to(ChangeStatus::Applied);
+
+        let history = get_member_history_impl(&mut connection, 1).await.unwrap();
+        assert_that!(history.len()).is_equal_to(1);
+        assert_that!(history[0].author).is_equal_to(get_author());
+        assert_that!(history[0].change.column.as_str()).is_equal_to("iban");
+        assert_that!(history[0].change.new_value)
+            .is_equal_to(ColumnValue::Text(String::from("DE02500105170137075030")));
+
+        let revert_result = revert_member_change_impl(&mut connection, history[0].change_id)
+            .await
+            .unwrap();
+        assert_that!(revert_result.record_results[0].status).is_equal_to(ChangeStatus::Applied);
+
+        let reverted_member = load_member_by_id(&mut connection, 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_that!(reverted_member.iban.as_str()).is_equal_to("DE02120300000000202051");
+
+        // Reverting the same change again conflicts with the current value
+        let repeated_revert_result =
+            revert_member_change_impl(&mut connection, history[0].change_id)
+                .await
+                .unwrap();
+        assert_that!(matches!(
+            repeated_revert_result.record_results[0].status,
+            ChangeStatus::Conflict(_)
+        ))
+        .is_true();
+
+        let history = get_member_history_impl(&mut connection, 1).await.unwrap();
+        assert_that!(history.len()).is_equal_to(2);
+        assert_that!(history[1].change_id).is_greater_than(history[0].change_id);
+
+        // Rolling back the repeated revert and its conflict
+        tear_down(2);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_history_and_revert_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_history_and_revert(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_history_and_revert_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_history_and_revert(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_history_and_revert_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_history_and_revert(setup_test(pool).await).await
+    }
+}
@@ -0,0 +1,397 @@
+use crate::api::connection_manager::with_connection;
+use crate::api::database_error::DatabaseError;
+use crate::api::member::{create_current_members_query, get_column_type};
+use crate::api::models;
+use crate::db_connection::DbConnection;
+use crate::json_field_conversion::FieldType;
+use crate::value_conversion::{matches_field_type, to_expr};
+use database_types::column_value::ColumnValue;
+use database_types::connection_description::ConnectionDescription;
+use log::error;
+use sea_query::Condition;
+use sea_query::Expr;
+use sea_query::ExprTrait;
+use sea_query::Func;
+use sea_query::LikeExpr;
+use sea_query::Order;
+use sea_query::SelectStatement;
+
+pub enum MemberFilter {
+    Equals {
+        column: String,
+        value: ColumnValue,
+    },
+    // Case insensitive substring match on text columns
+    Contains {
+        column: String,
+        text: String,
+    },
+    // Null bounds are unbounded
+    Range {
+        column: String,
+        min: ColumnValue,
+        max: ColumnValue,
+    },
+    IsNull {
+        column: String,
+    },
+    And(Vec<MemberFilter>),
+    Or(Vec<MemberFilter>),
+    Not(Box<MemberFilter>),
+}
+
+pub struct SortOrder {
+    pub column: String,
+    pub ascending: bool,
+}
+
+pub struct MemberQuery {
+    pub filter: Option<MemberFilter>,
+    pub sort_orders: Vec<SortOrder>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+fn check_column(column: &str, values: &[&ColumnValue]) -> bool {
+    let opt_type_info = get_column_type(column);
+
+    if opt_type_info.is_none() {
+        error!("Member has no column '{}'", column);
+        return false;
+    }
+
+    let type_info = opt_type_info.unwrap();
+
+    if values
+        .iter()
+        .any(|value| !matches_field_type(value, &type_info))
+    {
+        error!("Filter values do not match the type of column '{}'", column);
+        return false;
+    }
+
+    true
+}
+
+fn escape_like_pattern(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn create_condition(filter: &MemberFilter) -> Option<Condition> {
+    return match filter {
+        MemberFilter::Equals { column, value } => {
+            if !check_column(column, &[value]) {
+                return None;
+            }
+
+            Some(Condition::all().add(match value {
+                ColumnValue::Null => Expr::col(column.clone()).is_null(),
+                value => Expr::col(column.clone()).eq(to_expr(value)),
+            }))
+        }
+        MemberFilter::Contains { column, text } => {
+            if get_column_type(column)
+                .is_none_or(|type_info| type_info.field_type != FieldType::Text)
+            {
+                error!("Column '{}' is no text column", column);
+                return None;
+            }
+
+            let pattern = format!("%{}%", escape_like_pattern(&text.to_lowercase()));
+            Some(
+                Condition::all().add(
+                    Expr::expr(Func::lower(Expr::col(column.clone())))
+                        .like(LikeExpr::new(pattern).escape('\\')),
+                ),
+            )
+        }
+        MemberFilter::Range { column, min, max } => {
+            let bounds = [min, max]
+                .into_iter()
+                .filter(|bound| **bound != ColumnValue::Null)
+                .collect::<Vec<_>>();
+
+            if !check_column(column, &bounds) {
+                return None;
+            }
+
+            let mut condition = Condition::all();
+
+            if *min != ColumnValue::Null {
+                condition = condition.add(Expr::col(column.clone()).gte(to_expr(min)));
+            }
+
+            if *max != ColumnValue::Null {
+                condition = condition.add(Expr::col(column.clone()).lte(to_expr(max)));
+            }
+
+            Some(condition)
+        }
+        MemberFilter::IsNull { column } => {
+            if !check_column(column, &[]) {
+                return None;
+            }
+
+            Some(Condition::all().add(Expr::col(column.clone()).is_null()))
+        }
+        MemberFilter::And(filters) => filters
+            .iter()
+            .try_fold(Condition::all(), |condition, filter| {
+                Some(condition.add(create_condition(filter)?))
+            }),
+        MemberFilter::Or(filters) => filters
+            .iter()
+            .try_fold(Condition::any(), |condition, filter| {
+                Some(condition.add(create_condition(filter)?))
+            }),
+        MemberFilter::Not(filter) => create_condition(filter).map(Condition::not),
+    };
+}
+
+fn create_select_statement(query: &MemberQuery) -> Option<SelectStatement> {
+    let mut select_statement = create_current_members_query();
+
+    if query.filter.is_some() {
+        select_statement.cond_where(create_condition(query.filter.as_ref().unwrap())?);
+    }
+
+    for sort_order in &query.sort_orders {
+        if !check_column(&sort_order.column, &[]) {
+            return None;
+        }
+
+        select_statement.order_by(
+            sort_order.column.clone(),
+            if sort_order.ascending {
+                Order::Asc
+            } else {
+                Order::Desc
+            },
+        );
+    }
+
+    // Rows with equal sort values would be paginated in arbitrary order otherwise
+    if !query
+        .sort_orders
+        .iter()
+        .any(|sort_order| sort_order.column == "membership_id")
+    {
+        select_statement.order_by("membership_id", Order::Asc);
+    }
+
+    // MySQL and SQLite do not support OFFSET without LIMIT
+    if query.limit.is_some() {
+        select_statement.limit(query.limit.unwrap());
+    } else if query.offset.is_some() {
+        select_statement.limit(i64::MAX as u64);
+    }
+
+    if query.offset.is_some() {
+        select_statement.offset(query.offset.unwrap());
+    }
+
+    Some(select_statement)
+}
+
+async fn get_members_impl(
+    connection: &mut DbConnection,
+    query: MemberQuery,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    // The reason is logged while checking the columns and values
+    let opt_select_statement = create_select_statement(&query);
+
+    if opt_select_statement.is_none() {
+        return Err(DatabaseError::InvalidInput {
+            message: String::from("Query refers to unknown columns or mismatching values"),
+        });
+    }
+
+    connection
+        .load::<models::Member, _>(opt_select_statement.unwrap())
+        .await
+}
+
+pub fn get_members(
+    connection: ConnectionDescription,
+    query: MemberQuery,
+) -> Result<Vec<models::Member>, DatabaseError> {
+    with_connection(connection, async move |connection| {
+        get_members_impl(connection, query).await
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+
+    use speculoos::prelude::*;
+
+    use crate::api::member::create_member_impl;
+    use crate::test_database_common::{self, GetCurrentDBName};
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        let mut connection = test_database_common::setup_test(sqlx_pool).await;
+        test_database_common::create_member_tables(&mut connection).await;
+
+        for (membershipid, surname) in [
+            (1, "Doe"),
+            (2, "Smith"),
+            (3, "Doe"),
+            (4, "Smith"),
+            (5, "50%_Off"),
+        ] {
+            let values = test_database_common::create_member_values(surname);
+            assert_that!(
+                create_member_impl(&mut connection, Some(membershipid), values)
+                    .await
+                    .map(|member| member.membership_id)
+            )
+            .is_ok_containing(membershipid);
+        }
+
+        connection
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    async fn load_membership_ids(connection: &mut DbConnection, query: MemberQuery) -> Vec<i32> {
+        get_members_impl(connection, query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|member| member.membership_id)
+            .collect()
+    }
+
+    async fn test_get_members(mut connection: DbConnection) -> sqlx::Result<()> {
+        // Wildcards of LIKE are matched literally
+        let contains_query = MemberQuery {
+            filter: Some(MemberFilter::Contains {
+                column: "surname".to_owned(),
+                text: "0%_O".to_owned(),
+            }),
+            sort_orders: vec![],
+            limit: None,
+            offset: None,
+        };
+        assert_that!(load_membership_ids(&mut connection, contains_query).await)
+            .is_equal_to(vec![5]);
+
+        // Equal surnames are ordered by membership ID
+        let offset_query = MemberQuery {
+            filter: Some(MemberFilter::Range {
+                column: "membership_id".to_owned(),
+                min: ColumnValue::Integer(2),
+                max: ColumnValue::Null,
+            }),
+            sort_orders: vec![SortOrder {
+                column: "surname".to_owned(),
+                ascending: false,
+            }],
+            limit: None,
+            offset: Some(1),
+        };
+        assert_that!(load_membership_ids(&mut connection, offset_query).await)
+            .is_equal_to(vec![4, 3, 5]);
+
+        let page_query = MemberQuery {
+            filter: None,
+            sort_orders: vec![SortOrder {
+                column: "surname".to_owned(),
+                ascending: true,
+            }],
+            limit: Some(2),
+            offset: Some(1),
+        };
+        assert_that!(load_membership_ids(&mut connection, page_query).await)
+            .is_equal_to(vec![1, 3]);
+
+        tear_down(0);
+        Ok(())
+    }
+
+    fn create_query(filter: MemberFilter) -> MemberQuery {
+        MemberQuery {
+            filter: Some(filter),
+            sort_orders: vec![SortOrder {
+                column: "surname".to_owned(),
+                ascending: true,
+            }],
+            limit: Some(10),
+            offset: Some(20),
+        }
+    }
+
+    #[test]
+    fn test_create_select_statement() {
+        let valid_query = create_query(MemberFilter::And(vec![
+            MemberFilter::Contains {
+                column: "surname".to_owned(),
+                text: "50%_Off".to_owned(),
+            },
+            MemberFilter::Not(Box::new(MemberFilter::IsNull {
+                column: "title".to_owned(),
+            })),
+            MemberFilter::Range {
+                column: "membership_id".to_owned(),
+                min: ColumnValue::Integer(3),
+                max: ColumnValue::Null,
+            },
+        ]));
+        let opt_select_statement = create_select_statement(&valid_query);
+        assert_that!(opt_select_statement.is_some()).is_true();
+
+        let sql = opt_select_statement
+            .unwrap()
+            .to_string(sea_query::PostgresQueryBuilder);
+        assert_that!(sql).contains("LOWER(\"surname\") LIKE");
+        assert_that!(sql).contains("ESCAPE");
+        assert_that!(sql).contains(r#""membership_id" >= 3"#);
+        assert_that!(sql)
+            .contains(r#"ORDER BY "surname" ASC, "membership_id" ASC LIMIT 10 OFFSET 20"#);
+
+        let unknown_column_query = create_query(MemberFilter::IsNull {
+            column: "unknown_column".to_owned(),
+        });
+        assert_that!(create_select_statement(&unknown_column_query).is_none()).is_true();
+
+        let type_mismatch_query = create_query(MemberFilter::Or(vec![MemberFilter::Equals {
+            column: "membership_id".to_owned(),
+            value: ColumnValue::Text("1".to_owned()),
+        }]));
+        assert_that!(create_select_statement(&type_mismatch_query).is_none()).is_true();
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_get_members_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_get_members(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_get_members_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_get_members(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_get_members_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_get_members(setup_test(pool).await).await
+    }
+}
@@ -1,26 +1,119 @@
+pub mod command_template;
+pub mod connection_diagnostics;
+pub mod connection_manager;
+pub mod connection_url;
+pub mod database_error;
+pub mod dynamic_table;
+pub mod host_key;
 pub mod init;
 pub mod member;
+pub mod member_archive;
+pub mod member_audit;
+pub mod member_query;
 pub mod models;
+pub mod schema_version;
 
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+pub use database_types::column_value::ColumnValue;
 pub use database_types::connection_description::ConnectionDescription;
 pub use database_types::connection_description::DatabaseBackend;
+pub use database_types::connection_description::SshAuthentication;
+pub use database_types::connection_description::SshHop;
 pub use database_types::connection_description::SshTunnelDescription;
+pub use database_types::connection_description::SshTunnelMode;
+pub use database_types::connection_description::TimeoutOptions;
+pub use database_types::connection_description::TlsMode;
+pub use database_types::connection_description::TlsOptions;
 use flutter_rust_bridge::frb;
 
+#[frb(mirror(ColumnValue))]
+pub enum _ColumnValue {
+    Text(String),
+    Integer(i64),
+    Boolean(bool),
+    Double(f64),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    Null,
+}
+
+// flutter_rust_bridge does not support NaiveDate. Dates are transferred as DateTime at the start of their day.
+#[frb(rust2dart(dart_type = "DateTime", dart_code = "{}"))]
+pub fn encode_naive_date(raw: NaiveDate) -> NaiveDateTime {
+    raw.and_time(NaiveTime::MIN)
+}
+
+#[frb(dart2rust(dart_type = "DateTime", dart_code = "{}"))]
+pub fn decode_naive_date(raw: NaiveDateTime) -> NaiveDate {
+    raw.date()
+}
+
 #[frb(mirror(DatabaseBackend))]
 pub enum _DatabaseBackend {
     #[cfg(feature = "mysql")]
     MySql,
     #[cfg(feature = "postgres")]
     PostgreSql,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+#[frb(mirror(SshAuthentication))]
+pub enum _SshAuthentication {
+    Password(String),
+    PrivateKey {
+        path: String,
+        passphrase: Option<String>,
+    },
+    Agent,
+}
+
+#[frb(mirror(SshTunnelMode))]
+pub enum _SshTunnelMode {
+    PortForwarding,
+    RemoteCli,
+}
+
+#[frb(mirror(SshHop))]
+pub struct _SshHop {
+    pub username: String,
+    pub authentication: SshAuthentication,
+    pub host: String,
+    pub port: u16,
 }
 
 #[frb(mirror(SshTunnelDescription))]
 pub struct _SshTunnelDescription {
     pub username: String,
-    pub password: String,
+    pub authentication: SshAuthentication,
     pub host: String,
     pub port: u16,
+    pub jump_hosts: Vec<SshHop>,
+    pub mode: SshTunnelMode,
+}
+
+#[frb(mirror(TlsMode))]
+pub enum _TlsMode {
+    Disabled,
+    Preferred,
+    Required,
+    VerifyCa,
+    VerifyFull,
+}
+
+#[frb(mirror(TlsOptions))]
+pub struct _TlsOptions {
+    pub mode: TlsMode,
+    pub ca_certificate_path: Option<String>,
+    pub client_certificate_path: Option<String>,
+    pub client_key_path: Option<String>,
+}
+
+#[frb(mirror(TimeoutOptions))]
+pub struct _TimeoutOptions {
+    pub connect_seconds: u32,
+    pub authentication_seconds: u32,
+    pub query_seconds: u32,
 }
 
 #[frb(mirror(ConnectionDescription))]
@@ -32,4 +125,8 @@ pub struct _ConnectionDescription {
     pub password: String,
     pub name: String,
     pub ssh_tunnel: Option<SshTunnelDescription>,
+    pub tls: TlsOptions,
+    pub socket_path: Option<String>,
+    pub cli_command_template: Option<String>,
+    pub timeouts: TimeoutOptions,
 }
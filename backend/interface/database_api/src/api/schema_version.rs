@@ -0,0 +1,176 @@
+use crate::api::connection_manager::with_connection;
+use crate::api::database_error::DatabaseError;
+use crate::api::dynamic_table::has_table;
+use crate::db_connection::DbConnection;
+use crate::migration::{MIGRATIONS, get_latest_version};
+use database_types::connection_description::ConnectionDescription;
+use flutter_rust_bridge::frb;
+use log::{error, info};
+use sea_query::Expr;
+use sea_query::Query;
+
+// Databases which were set up before versioning the schema do not have the table yet
+pub(crate) async fn get_schema_version_impl(
+    connection: &mut DbConnection,
+) -> Result<i32, DatabaseError> {
+    let has_table_result = has_table(connection, "schema_version").await;
+
+    if has_table_result.is_err() {
+        error!("Could not determine schema version");
+        return Err(has_table_result.err().unwrap());
+    }
+
+    if !has_table_result.unwrap() {
+        return Ok(0);
+    }
+
+    let select_statement = Query::select()
+        .expr_as(Expr::col("version"), "value")
+        .from("schema_version")
+        .to_owned();
+
+    let versions = connection.load_integers(select_statement).await?;
+    Ok(versions.into_iter().max().unwrap_or(0))
+}
+
+pub(crate) async fn upgrade_schema_impl(
+    connection: &mut DbConnection,
+) -> Result<i32, DatabaseError> {
+    let current_version = get_schema_version_impl(connection).await?;
+    let latest_version = get_latest_version();
+
+    if current_version > latest_version {
+        let message = format!(
+            "Schema version {} is newer than the latest known version {}",
+            current_version, latest_version
+        );
+        error!("{}", message);
+        return Err(DatabaseError::UnexpectedResult { message });
+    }
+
+    /* NOTE 2026-10-18: MySQL implicitly commits DDL statements. Therefore migrations are not applied atomically and
+     * their statements should be idempotent (e.g. IF NOT EXISTS) such that an interrupted upgrade can be repeated. The
+     * registration in schema_version is not idempotent. Being the last statement of each migration it only runs after
+     * all other statements succeeded and the registered migration is never applied again.
+     */
+    let backend = connection.get_backend();
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > current_version)
+    {
+        info!(
+            "Applying migration {} '{}'",
+            migration.version, migration.description
+        );
+
+        for statement in migration.get_up_statements(&backend) {
+            let statement_result = connection.execute_sql(statement).await;
+
+            if statement_result.is_err() {
+                error!(
+                    "Migration {} failed. The schema may be upgraded partially.",
+                    migration.version
+                );
+                return Err(statement_result.err().unwrap());
+            }
+        }
+    }
+
+    let upgraded_version = get_schema_version_impl(connection).await?;
+
+    if upgraded_version != latest_version {
+        let message = format!(
+            "Schema has version {} instead of {} after upgrading",
+            upgraded_version, latest_version
+        );
+        error!("{}", message);
+        return Err(DatabaseError::UnexpectedResult { message });
+    }
+
+    Ok(upgraded_version)
+}
+
+// Version 0 denotes a database without any migrations applied
+pub fn get_schema_version(connection: ConnectionDescription) -> Result<i32, DatabaseError> {
+    with_connection(connection, async |connection| {
+        get_schema_version_impl(connection).await
+    })
+}
+
+#[frb(sync)]
+pub fn get_latest_schema_version() -> i32 {
+    get_latest_version()
+}
+
+// Apply all pending migrations and return the resulting schema version
+pub fn upgrade_schema(connection: ConnectionDescription) -> Result<i32, DatabaseError> {
+    with_connection(connection, async |connection| {
+        upgrade_schema_impl(connection).await
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "mysql")]
+    use sqlx::MySqlPool;
+    #[cfg(feature = "postgres")]
+    use sqlx::PgPool;
+    #[cfg(feature = "sqlite")]
+    use sqlx::SqlitePool;
+    use sqlx::{Database, Pool};
+
+    use speculoos::prelude::*;
+
+    use crate::api::member::get_all_members_impl;
+    use crate::test_database_common::{self, GetCurrentDBName};
+
+    use super::*;
+
+    async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+    where
+        DB: Database + GetCurrentDBName,
+    {
+        test_database_common::setup_test(sqlx_pool).await
+    }
+
+    fn tear_down(expected_num_severe_messages: usize) {
+        test_database_common::tear_down(expected_num_severe_messages);
+    }
+
+    async fn test_upgrade_schema(mut connection: DbConnection) -> sqlx::Result<()> {
+        assert_that!(get_schema_version_impl(&mut connection).await).is_equal_to(Ok(0));
+        // Determining the version does not create any table
+        assert_that!(has_table(&mut connection, "schema_version").await).is_equal_to(Ok(false));
+
+        let latest_version = get_latest_version();
+        assert_that!(upgrade_schema_impl(&mut connection).await).is_equal_to(Ok(latest_version));
+
+        // Upgrading an up to date schema has no effect
+        assert_that!(upgrade_schema_impl(&mut connection).await).is_equal_to(Ok(latest_version));
+
+        let members_result = get_all_members_impl(&mut connection).await;
+        assert_that!(members_result.map(|members| members.len())).is_equal_to(Ok(0));
+
+        tear_down(0);
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    #[sqlx::test]
+    async fn test_upgrade_schema_pg(pool: PgPool) -> sqlx::Result<()> {
+        test_upgrade_schema(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[sqlx::test]
+    async fn test_upgrade_schema_mysql(pool: MySqlPool) -> sqlx::Result<()> {
+        test_upgrade_schema(setup_test(pool).await).await
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[sqlx::test]
+    async fn test_upgrade_schema_sqlite(pool: SqlitePool) -> sqlx::Result<()> {
+        test_upgrade_schema(setup_test(pool).await).await
+    }
+}
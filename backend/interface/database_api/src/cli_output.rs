@@ -0,0 +1,388 @@
+use database_types::connection_description::DatabaseBackend;
+use log::error;
+
+// Result of a query printed by a CLI client where None represents NULL
+#[derive(Debug, Default, PartialEq)]
+pub struct CliTable {
+    pub column_names: Vec<String>,
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+// Return the fields of all records and whether each field was quoted
+fn parse_csv_records(text: &str) -> Option<Vec<Vec<(String, bool)>>> {
+    let mut records = vec![];
+
+    if text.is_empty() {
+        return Some(records);
+    }
+
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() && !is_quoted => {
+                is_quoted = true;
+
+                // Read until the closing quote while unescaping doubled quotes
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(quoted_char) => field.push(quoted_char),
+                        None => {
+                            error!("CSV output ends within quoted field '{}'", field);
+                            return None;
+                        }
+                    }
+                }
+
+                if chars
+                    .peek()
+                    .is_some_and(|next_char| *next_char != ',' && *next_char != '\n')
+                {
+                    error!(
+                        "CSV output has characters following the quoted field '{}'",
+                        field
+                    );
+                    return None;
+                }
+            }
+            ',' => {
+                record.push((std::mem::take(&mut field), is_quoted));
+                is_quoted = false;
+            }
+            '\n' => {
+                record.push((std::mem::take(&mut field), is_quoted));
+                records.push(std::mem::take(&mut record));
+                is_quoted = false;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    record.push((field, is_quoted));
+    records.push(record);
+
+    Some(records)
+}
+
+/* Wrap the query so the CLI prints NULL distinguishable from any text. MySQL requires the names of the columns since
+ * each one is quoted separately.
+ */
+#[cfg_attr(not(feature = "mysql"), allow(unused_variables))]
+pub fn wrap_cli_query(backend: DatabaseBackend, sql_query: &str, column_names: &[&str]) -> String {
+    match backend {
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => {
+            format!("COPY ({}) TO STDOUT WITH (FORMAT csv, HEADER)", sql_query)
+        }
+        /* NOTE 2026-10-18: MySQL propagates the ORDER BY of the derived table to the outer query since the outer query
+         * neither groups, sorts nor joins
+         */
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => {
+            let quoted_columns = column_names
+                .iter()
+                .map(|column_name| {
+                    let identifier = format!("`{}`", column_name.replace('`', "``"));
+                    format!("QUOTE({0}) AS {0}", identifier)
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!(
+                "SELECT {} FROM ({}) AS cli_result",
+                quoted_columns, sql_query
+            )
+        }
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => unreachable!("SQLite databases are not accessed via SSH"),
+    }
+}
+
+/* COPY ... TO STDOUT WITH (FORMAT csv) quotes values containing separators, quotes or line breaks as well as empty
+ * text. Therefore only unquoted empty fields represent NULL.
+ */
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub fn parse_psql_csv(output_lines: &[String]) -> Option<CliTable> {
+    let records = parse_csv_records(&output_lines.join("\n"))?;
+    let opt_records_split = records.split_first();
+
+    if opt_records_split.is_none() {
+        return Some(CliTable::default());
+    }
+
+    let (column_names, rows) = opt_records_split.unwrap();
+
+    Some(CliTable {
+        column_names: column_names
+            .iter()
+            .map(|(column_name, _)| column_name.clone())
+            .collect(),
+        rows: rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|(value, is_quoted)| {
+                        if !is_quoted && value.is_empty() {
+                            None
+                        } else {
+                            Some(value.clone())
+                        }
+                    })
+                    .collect()
+            })
+            .collect(),
+    })
+}
+
+#[cfg_attr(not(feature = "mysql"), allow(dead_code))]
+fn unescape_mysql_field(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('0') => unescaped.push('\0'),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(escaped_char) => unescaped.push(escaped_char),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+// Reverse QUOTE() which encloses text in single quotes and escapes backslashes, quotes, NUL and Control+Z
+#[cfg_attr(not(feature = "mysql"), allow(dead_code))]
+fn unquote_mysql_value(value: &str) -> Option<String> {
+    let opt_inner = value
+        .strip_prefix('\'')
+        .and_then(|unprefixed| unprefixed.strip_suffix('\''));
+
+    if opt_inner.is_none() {
+        error!("MySQL value '{}' is neither quoted nor NULL", value);
+        return None;
+    }
+
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = opt_inner.unwrap().chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('0') => unquoted.push('\0'),
+            Some('Z') => unquoted.push('\x1a'),
+            Some(escaped_char) => unquoted.push(escaped_char),
+            None => unquoted.push('\\'),
+        }
+    }
+
+    Some(unquoted)
+}
+
+/* mysql --batch separates fields by tabs and escapes tabs, line breaks and backslashes within values. Since it prints
+ * NULL like the text "NULL", all values are expected to be quoted by QUOTE() (see wrap_cli_query).
+ */
+#[cfg_attr(not(feature = "mysql"), allow(dead_code))]
+pub fn parse_mysql_batch(output_lines: &[String]) -> Option<CliTable> {
+    let opt_lines_split = output_lines.split_first();
+
+    // mysql does not print anything (not even the column names) for empty results
+    if opt_lines_split.is_none() {
+        return Some(CliTable::default());
+    }
+
+    let (header, rows) = opt_lines_split.unwrap();
+    let mut table = CliTable {
+        column_names: header.split('\t').map(unescape_mysql_field).collect(),
+        rows: vec![],
+    };
+
+    for row in rows {
+        let mut values = vec![];
+
+        for field in row.split('\t') {
+            let value = unescape_mysql_field(field);
+
+            if value == "NULL" {
+                values.push(None);
+            } else {
+                values.push(Some(unquote_mysql_value(&value)?));
+            }
+        }
+
+        table.rows.push(values);
+    }
+
+    Some(table)
+}
+
+#[cfg_attr(
+    not(any(feature = "mysql", feature = "postgres")),
+    allow(unused_variables)
+)]
+pub fn parse_cli_table(backend: DatabaseBackend, output_lines: &[String]) -> Option<CliTable> {
+    match backend {
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql => validate_cli_table(parse_psql_csv(output_lines)),
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql => validate_cli_table(parse_mysql_batch(output_lines)),
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => unreachable!("SQLite databases are not accessed via SSH"),
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+fn validate_cli_table(opt_table: Option<CliTable>) -> Option<CliTable> {
+    if opt_table.as_ref().is_some_and(|table| {
+        table
+            .rows
+            .iter()
+            .any(|row| row.len() != table.column_names.len())
+    }) {
+        error!("There are rows of different size than the row of column names");
+        return None;
+    }
+
+    opt_table
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn to_lines(output: &str) -> Vec<String> {
+        output.lines().map(ToOwned::to_owned).collect()
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_wrap_cli_query_pg() {
+        assert_that!(wrap_cli_query(
+            DatabaseBackend::PostgreSql,
+            "SELECT 1 AS value",
+            &["value"]
+        ))
+        .is_equal_to(String::from(
+            "COPY (SELECT 1 AS value) TO STDOUT WITH (FORMAT csv, HEADER)",
+        ));
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_wrap_cli_query_mysql() {
+        assert_that!(wrap_cli_query(
+            DatabaseBackend::MySql,
+            "SELECT * FROM `member`",
+            &["membership_id", "odd`name"]
+        ))
+        .is_equal_to(String::from(
+            "SELECT QUOTE(`membership_id`) AS `membership_id`, QUOTE(`odd``name`) AS `odd``name` \
+             FROM (SELECT * FROM `member`) AS cli_result",
+        ));
+    }
+
+    #[test]
+    fn test_parse_psql_csv() {
+        // Output of COPY (...) TO STDOUT WITH (FORMAT csv, HEADER)
+        let output = to_lines(
+            "membership_id,street,title,note\n\
+             1,\"Main St, 5\",NULL,\n\
+             2,\"First line\nSecond \"\"quoted\"\" line\",,\\N\n\
+             3,\"\",\"\",",
+        );
+
+        let table = parse_psql_csv(&output).unwrap();
+        assert_that!(table.column_names).is_equal_to(vec![
+            String::from("membership_id"),
+            String::from("street"),
+            String::from("title"),
+            String::from("note"),
+        ]);
+        assert_that!(table.rows).is_equal_to(vec![
+            vec![
+                Some(String::from("1")),
+                Some(String::from("Main St, 5")),
+                Some(String::from("NULL")),
+                None,
+            ],
+            vec![
+                Some(String::from("2")),
+                Some(String::from("First line\nSecond \"quoted\" line")),
+                None,
+                Some(String::from("\\N")),
+            ],
+            vec![
+                Some(String::from("3")),
+                Some(String::new()),
+                Some(String::new()),
+                None,
+            ],
+        ]);
+
+        // Single NULL columns result in empty lines
+        let table = parse_psql_csv(&to_lines("value\n\n42")).unwrap();
+        assert_that!(table.rows).is_equal_to(vec![vec![None], vec![Some(String::from("42"))]]);
+
+        assert_that!(parse_psql_csv(&to_lines("value\n\"unterminated"))).is_none();
+        assert_that!(parse_psql_csv(&[])).is_equal_to(Some(CliTable::default()));
+    }
+
+    #[test]
+    fn test_parse_mysql_batch() {
+        // Output of mysql --batch for values quoted by QUOTE()
+        let output = to_lines(
+            "membership_id\tstreet\ttitle\tnote\n\
+             '1'\t'Main\\tSt'\t'NULL'\tNULL\n\
+             '2'\t'First line\\nC:\\\\\\\\path'\tNULL\t'it\\\\'s'\n\
+             '3'\t''\t'\\\\0'\t'NULL'",
+        );
+
+        let table = parse_mysql_batch(&output).unwrap();
+        assert_that!(table.column_names.len()).is_equal_to(4);
+        assert_that!(table.rows).is_equal_to(vec![
+            vec![
+                Some(String::from("1")),
+                Some(String::from("Main\tSt")),
+                Some(String::from("NULL")),
+                None,
+            ],
+            vec![
+                Some(String::from("2")),
+                Some(String::from("First line\nC:\\path")),
+                None,
+                Some(String::from("it's")),
+            ],
+            vec![
+                Some(String::from("3")),
+                Some(String::new()),
+                Some(String::from("\0")),
+                Some(String::from("NULL")),
+            ],
+        ]);
+
+        // Values which are not quoted by QUOTE()
+        assert_that!(parse_mysql_batch(&to_lines("value\n42"))).is_none();
+        assert_that!(parse_mysql_batch(&to_lines("value\n'"))).is_none();
+        assert_that!(parse_mysql_batch(&[])).is_equal_to(Some(CliTable::default()));
+    }
+}
@@ -1,34 +1,79 @@
+use crate::api::command_template::render_command_template;
+use crate::api::connection_manager::RUNTIME;
+use crate::api::connection_url::build_connection_url;
+use crate::api::database_error::DatabaseError;
+use crate::api::host_key::{HostKeyStatus, get_known_hosts_path, verify_host_key};
 use crate::orm_connection::OrmConnection;
 use database_types::connection_description::DatabaseBackend;
-use database_types::connection_description::{ConnectionDescription, SshTunnelDescription};
-use diesel::Connection;
+use database_types::connection_description::{
+    ConnectionDescription, SshAuthentication, SshHop, SshTunnelDescription, SshTunnelMode,
+    TimeoutOptions, TlsMode,
+};
 #[cfg(feature = "mysql")]
 use diesel::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::PgConnection;
-use log::error;
-use std::sync::Arc;
+use diesel::{Connection, ConnectionError};
+#[cfg(feature = "sqlite")]
+use diesel::{RunQueryDsl, SqliteConnection};
+use log::{error, warn};
+use std::io::ErrorKind;
+use std::sync::{Arc, Mutex};
+use tokio::task::spawn_blocking;
+use tokio::time::timeout;
 
 use russh::client;
+use russh::client::AuthResult;
+#[cfg(unix)]
+use russh::keys::agent::client::AgentClient;
+use russh::keys::{HashAlg, PrivateKeyWithHashAlg, PublicKey, load_secret_key};
 
 use crate::db_connection::DbConnection;
+use crate::port_forwarding::start_port_forwarding;
 use crate::ssh_connection::SshConnection;
 
-struct SshClient {}
+// Key presented by the server during the last handshake and the result of verifying it
+type HostKeyObservation = Arc<Mutex<Option<(HostKeyStatus, PublicKey)>>>;
+
+pub struct SshClient {
+    host: String,
+    port: u16,
+    host_key_observation: HostKeyObservation,
+    // Session of the previous hop which has to outlive the session tunneled through it
+    _opt_jump_session: Option<Arc<client::Handle<SshClient>>>,
+}
 
 impl client::Handler for SshClient {
     type Error = russh::Error;
 
     async fn check_server_key(
         &mut self,
-        _server_public_key: &russh::keys::ssh_key::PublicKey,
+        server_public_key: &PublicKey,
     ) -> Result<bool, Self::Error> {
-        // FIXME Do not accept all SSH servers
-        Ok(true)
+        let host_key_status = verify_host_key(
+            &get_known_hosts_path(),
+            &self.host,
+            self.port,
+            server_public_key,
+        );
+        let is_trusted = host_key_status == HostKeyStatus::Trusted;
+
+        *self.host_key_observation.lock().unwrap() =
+            Some((host_key_status, server_public_key.clone()));
+
+        Ok(is_trusted)
     }
 }
 
-async fn create_ssh_client(host: &str, port: u16) -> Option<client::Handle<SshClient>> {
+pub(crate) async fn connect_ssh_client(
+    host: &str,
+    port: u16,
+    opt_jump_session: Option<Arc<client::Handle<SshClient>>>,
+    timeouts: &TimeoutOptions,
+) -> (
+    Result<client::Handle<SshClient>, russh::Error>,
+    Option<(HostKeyStatus, PublicKey)>,
+) {
     let config = client::Config {
         nodelay: true,
         ..Default::default()
@@ -36,163 +81,515 @@ async fn create_ssh_client(host: &str, port: u16) -> Option<client::Handle<SshCl
 
     let arc_config = Arc::new(config);
 
-    let ssh_client = SshClient {};
+    let host_key_observation = HostKeyObservation::default();
+
+    let ssh_client = SshClient {
+        host: host.to_owned(),
+        port,
+        host_key_observation: host_key_observation.clone(),
+        _opt_jump_session: opt_jump_session.clone(),
+    };
+
+    let connection_future = async {
+        match opt_jump_session {
+            None => client::connect(arc_config, (host, port), ssh_client).await,
+            // Tunnel the SSH connection to the next hop through the session of the previous one
+            Some(jump_session) => match jump_session
+                .channel_open_direct_tcpip(host, port as u32, "127.0.0.1", 0)
+                .await
+            {
+                Ok(channel) => {
+                    client::connect_stream(arc_config, channel.into_stream(), ssh_client).await
+                }
+                Err(error) => Err(error),
+            },
+        }
+    };
+
+    let connection_result = match timeout(timeouts.get_connect_timeout(), connection_future).await {
+        Ok(connection_result) => connection_result,
+        Err(_) => Err(russh::Error::from(std::io::Error::new(
+            ErrorKind::TimedOut,
+            format!(
+                "Connecting timed out after {} seconds",
+                timeouts.connect_seconds
+            ),
+        ))),
+    };
+    let opt_observation = host_key_observation.lock().unwrap().take();
+
+    (connection_result, opt_observation)
+}
+
+// Connect without authenticating only for determining the host key
+pub(crate) async fn probe_host_key(
+    host: &str,
+    port: u16,
+    opt_jump_session: Option<Arc<client::Handle<SshClient>>>,
+    timeouts: &TimeoutOptions,
+) -> Option<(HostKeyStatus, PublicKey)> {
+    let (_connection_result, opt_observation) =
+        connect_ssh_client(host, port, opt_jump_session, timeouts).await;
+
+    if opt_observation.is_none() {
+        error!(
+            "SSH server '{}:{}' did not present any host key",
+            host, port
+        );
+    }
+
+    opt_observation
+}
 
-    let connection_result = client::connect(arc_config, (host, port), ssh_client).await;
+async fn create_ssh_client(
+    host: &str,
+    port: u16,
+    opt_jump_session: Option<Arc<client::Handle<SshClient>>>,
+    timeouts: &TimeoutOptions,
+) -> Result<client::Handle<SshClient>, DatabaseError> {
+    let (connection_result, opt_observation) =
+        connect_ssh_client(host, port, opt_jump_session, timeouts).await;
 
     match connection_result {
-        Ok(ssh_session) => Some(ssh_session),
+        Ok(ssh_session) => Ok(ssh_session),
         Err(error) => {
+            match opt_observation {
+                Some((HostKeyStatus::Unknown { fingerprint }, _)) => {
+                    let message = format!(
+                        "Host key '{}' of '{}:{}' has to be accepted before connecting",
+                        fingerprint, host, port
+                    );
+                    error!("{}", message);
+                    return Err(DatabaseError::HostKeyUnknown {
+                        message,
+                        fingerprint,
+                    });
+                }
+                Some((
+                    HostKeyStatus::Mismatch {
+                        fingerprint,
+                        known_fingerprint,
+                        ..
+                    },
+                    _,
+                )) => {
+                    let message = format!(
+                        "Host key '{}' of '{}:{}' does not match the known key '{}'",
+                        fingerprint, host, port, known_fingerprint
+                    );
+                    error!("{}", message);
+                    return Err(DatabaseError::HostKeyMismatch {
+                        message,
+                        known_fingerprint,
+                        presented_fingerprint: fingerprint,
+                    });
+                }
+                _ => {}
+            }
+
             error!("Could not create SSH session due '{}'", error);
-            None
+            Err(DatabaseError::from(error))
         }
     }
 }
 
-async fn authenticate_ssh_client(
-    ssh_session: &mut client::Handle<SshClient>,
-    username: &str,
-    password: &str,
+fn check_authentication_result(
+    authentication_result: Result<AuthResult, russh::Error>,
+    method: &str,
 ) -> bool {
-    let authentication_result = ssh_session.authenticate_password(username, password).await;
-
     match authentication_result {
-        Ok(authentication) => authentication.success(),
+        Ok(authentication) => {
+            if !authentication.success() {
+                error!("SSH authentication via {} was rejected", method);
+            }
+            authentication.success()
+        }
         Err(error) => {
-            error!("SSH authentication failed due '{}'", error);
+            error!("SSH authentication via {} failed due '{}'", method, error);
             false
         }
     }
 }
 
-async fn setup_ssh_client(description: &SshTunnelDescription) -> Option<client::Handle<SshClient>> {
-    let ssh_session_result = create_ssh_client(&description.host, description.port).await;
+async fn get_rsa_hash_algorithm(ssh_session: &client::Handle<SshClient>) -> Option<HashAlg> {
+    ssh_session
+        .best_supported_rsa_hash()
+        .await
+        .ok()
+        .flatten()
+        .flatten()
+}
+
+async fn authenticate_via_private_key(
+    ssh_session: &mut client::Handle<SshClient>,
+    username: &str,
+    path: &str,
+    passphrase: Option<&str>,
+) -> bool {
+    let private_key_result = load_secret_key(path, passphrase);
 
-    if ssh_session_result.is_none() {
-        return None;
+    if private_key_result.is_err() {
+        error!(
+            "Could not load private key '{}' due '{}'",
+            path,
+            private_key_result.err().unwrap()
+        );
+        return false;
     }
 
-    let mut ssh_session = ssh_session_result.unwrap();
+    let private_key = PrivateKeyWithHashAlg::new(
+        Arc::new(private_key_result.unwrap()),
+        get_rsa_hash_algorithm(ssh_session).await,
+    );
 
-    if !authenticate_ssh_client(
-        &mut ssh_session,
-        &description.username,
-        &description.password,
+    check_authentication_result(
+        ssh_session
+            .authenticate_publickey(username, private_key)
+            .await,
+        "private key",
     )
-    .await
-    {
-        return None;
+}
+
+#[cfg(unix)]
+async fn authenticate_via_agent(
+    ssh_session: &mut client::Handle<SshClient>,
+    username: &str,
+) -> bool {
+    let agent_result = AgentClient::connect_env().await;
+
+    if agent_result.is_err() {
+        error!(
+            "Could not connect to SSH agent due '{}'",
+            agent_result.err().unwrap()
+        );
+        return false;
     }
 
-    return Some(ssh_session);
-}
+    let mut agent = agent_result.unwrap();
+    let identities_result = agent.request_identities().await;
 
-pub async fn get_connection(connection: ConnectionDescription) -> Option<DbConnection> {
-    let db_host = connection.host;
-    let db_port = connection.port;
+    if identities_result.is_err() {
+        error!(
+            "Could not request identities of SSH agent due '{}'",
+            identities_result.err().unwrap()
+        );
+        return false;
+    }
 
-    if connection.ssh_tunnel.is_some() {
-        let ssh_tunnel_description = connection.ssh_tunnel.unwrap();
+    let hash_algorithm = get_rsa_hash_algorithm(ssh_session).await;
 
-        let opt_ssh_client = setup_ssh_client(&ssh_tunnel_description).await;
+    // Try all keys of the agent until one is accepted
+    for identity in identities_result.unwrap() {
+        let authentication_result = ssh_session
+            .authenticate_publickey_with(
+                username,
+                identity.public_key().into_owned(),
+                hash_algorithm,
+                &mut agent,
+            )
+            .await;
 
-        if opt_ssh_client.is_none() {
-            return None;
+        match authentication_result {
+            Ok(authentication) if authentication.success() => return true,
+            Ok(_) => {}
+            Err(error) => warn!("Authenticating via SSH agent key failed due '{}'", error),
         }
+    }
+
+    error!("SSH agent has no key accepted for user '{}'", username);
+    false
+}
+
+// FIXME Support SSH agents on Windows (named pipes)
+#[cfg(not(unix))]
+async fn authenticate_via_agent(
+    _ssh_session: &mut client::Handle<SshClient>,
+    _username: &str,
+) -> bool {
+    error!("SSH agents are only supported on Unix");
+    false
+}
 
-        let ssh_client = opt_ssh_client.unwrap();
-        let opt_channel = ssh_client.channel_open_session().await;
-
-        return match opt_channel {
-            Ok(channel) => Some(DbConnection::SshBased(match connection.backend {
-                #[cfg(feature = "postgres")]
-                DatabaseBackend::PostgreSql => SshConnection {
-                    channel,
-                    // FIXME What about character set?
-                    sql_login_command: format!(
-                        // NOTE stderr contains "Password for user XY:" resulting in a warning making the test fail
-                        "psql -d {database} -h {host} -p {port} -U {user} -W 2>/dev/null",
-                        host = db_host,
-                        port = db_port,
-                        user = connection.user,
-                        database = connection.name
-                    ),
-                    password: connection.password,
-                    backend: connection.backend,
-                },
-                // FIXME What about mariadb?
-                #[cfg(feature = "mysql")]
-                DatabaseBackend::MySql => SshConnection {
-                    channel,
-                    sql_login_command: format!(
-                        // NOTE stderr contains "Enter password:" resulting in a warning making the test fail
-                        "mysql --default-character-set=utf8mb4 -B {database} -h {host} -P {port} -u {user} -p 2>/dev/null",
-                        host = db_host,
-                        port = db_port,
-                        user = connection.user,
-                        database = connection.name
-                    ),
-                    password: connection.password,
-                    backend: connection.backend,
-                },
-            })),
-            Err(error) => {
-                error!("Could not create SSH session due '{}'", error);
-                return None;
+pub(crate) async fn authenticate_ssh_client(
+    ssh_session: &mut client::Handle<SshClient>,
+    username: &str,
+    authentication: &SshAuthentication,
+    timeouts: &TimeoutOptions,
+) -> Result<(), DatabaseError> {
+    let authentication_future = async {
+        match authentication {
+            SshAuthentication::Password(password) => check_authentication_result(
+                ssh_session.authenticate_password(username, password).await,
+                "password",
+            ),
+            SshAuthentication::PrivateKey { path, passphrase } => {
+                authenticate_via_private_key(ssh_session, username, path, passphrase.as_deref())
+                    .await
             }
-        };
+            SshAuthentication::Agent => authenticate_via_agent(ssh_session, username).await,
+        }
+    };
+
+    let timeout_result =
+        timeout(timeouts.get_authentication_timeout(), authentication_future).await;
+
+    if timeout_result.is_err() {
+        let message = format!(
+            "SSH authentication of user '{}' timed out after {} seconds",
+            username, timeouts.authentication_seconds
+        );
+        error!("{}", message);
+        return Err(DatabaseError::Timeout { message });
+    }
+
+    // The reason of the rejection is logged by the authentication methods
+    if !timeout_result.unwrap() {
+        return Err(DatabaseError::Authentication {
+            message: format!("SSH server rejected user '{}'", username),
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn setup_ssh_hop(
+    hop: &SshHop,
+    opt_jump_session: Option<Arc<client::Handle<SshClient>>>,
+    timeouts: &TimeoutOptions,
+) -> Result<client::Handle<SshClient>, DatabaseError> {
+    let mut ssh_session =
+        create_ssh_client(&hop.host, hop.port, opt_jump_session, timeouts).await?;
+
+    let authentication_result = authenticate_ssh_client(
+        &mut ssh_session,
+        &hop.username,
+        &hop.authentication,
+        timeouts,
+    )
+    .await;
+
+    if authentication_result.is_err() {
+        error!("Could not authenticate at '{}:{}'", hop.host, hop.port);
+        return Err(authentication_result.err().unwrap());
+    }
+
+    return Ok(ssh_session);
+}
+
+// Connect to every hop through the session of the previous one
+async fn setup_ssh_client(
+    description: &SshTunnelDescription,
+    timeouts: &TimeoutOptions,
+) -> Result<client::Handle<SshClient>, DatabaseError> {
+    let hops = description.get_hops();
+    let (target_hop, jump_hops) = hops.split_last().unwrap();
+    let mut opt_jump_session = None;
+
+    for jump_hop in jump_hops {
+        let jump_session = setup_ssh_hop(jump_hop, opt_jump_session, timeouts).await?;
+        opt_jump_session = Some(Arc::new(jump_session));
     }
 
-    match connection.backend {
+    setup_ssh_hop(target_hop, opt_jump_session, timeouts).await
+}
+
+// SQLite does not enforce foreign keys unless enabled for every connection
+#[cfg(feature = "sqlite")]
+fn establish_sqlite_connection(path: &str) -> Result<SqliteConnection, ConnectionError> {
+    let mut sqlite_connection = SqliteConnection::establish(path)?;
+
+    let pragma_result =
+        diesel::sql_query("PRAGMA foreign_keys = ON").execute(&mut sqlite_connection);
+
+    if pragma_result.is_err() {
+        return Err(ConnectionError::CouldntSetupConfiguration(
+            pragma_result.err().unwrap(),
+        ));
+    }
+
+    Ok(sqlite_connection)
+}
+
+#[cfg_attr(
+    not(any(feature = "postgres", feature = "mysql")),
+    allow(unused_variables)
+)]
+fn establish_orm_connection_blocking(
+    connection: &ConnectionDescription,
+    host: &str,
+    port: u16,
+) -> Result<OrmConnection, ConnectionError> {
+    let database_url = build_connection_url(connection, host, port);
+
+    return match connection.backend {
         #[cfg(feature = "postgres")]
         DatabaseBackend::PostgreSql => {
-            let database_url: String = format!(
-                "postgres://{user}:{password}@{host}:{port}/{name}",
-                user = connection.user,
-                password = connection.password,
-                host = db_host,
-                port = db_port,
-                name = connection.name
-            );
-            let connection = PgConnection::establish(&database_url);
-
-            if connection.is_ok() {
-                return Some(DbConnection::OrmBased(OrmConnection::PostgreSql(
-                    connection.unwrap(),
-                )));
-            }
-
-            error!(
-                "Connecting to database via '{}' failed due '{}'",
-                database_url,
-                connection.err().unwrap()
-            );
-            return None;
+            PgConnection::establish(&database_url).map(OrmConnection::PostgreSql)
         }
         #[cfg(feature = "mysql")]
         DatabaseBackend::MySql => {
-            let database_url: String = format!(
-                "mysql://{user}:{password}@{host}:{port}/{name}",
-                user = connection.user,
-                password = connection.password,
-                host = db_host,
-                port = db_port,
-                name = connection.name
-            );
-            let connection = MysqlConnection::establish(&database_url);
-
-            if connection.is_ok() {
-                return Some(DbConnection::OrmBased(OrmConnection::MySql(
-                    connection.unwrap(),
-                )));
-            }
-
-            error!(
-                "Connecting to database via '{}' failed due '{}'",
-                database_url,
-                connection.err().unwrap()
-            );
-            return None;
+            MysqlConnection::establish(&database_url).map(OrmConnection::MySql)
+        }
+        // The file is opened directly and created if it does not exist yet
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite => {
+            establish_sqlite_connection(&connection.name).map(OrmConnection::Sqlite)
         }
+    };
+}
+
+/* NOTE 2026-10-18: diesel does not support connect timeouts for all backends. Therefore connecting runs on a blocking
+ * thread which is abandoned when timing out. Its connection is closed as soon as connecting completes.
+ */
+pub(crate) async fn try_establish_orm_connection(
+    connection: &ConnectionDescription,
+    host: &str,
+    port: u16,
+) -> Result<OrmConnection, DatabaseError> {
+    let connection_description = connection.clone();
+    let host = host.to_owned();
+    let establish_task = spawn_blocking(move || {
+        establish_orm_connection_blocking(&connection_description, &host, port)
+    });
+
+    return match timeout(connection.timeouts.get_connect_timeout(), establish_task).await {
+        Ok(Ok(connection_result)) => connection_result.map_err(DatabaseError::from),
+        Ok(Err(join_error)) => Err(DatabaseError::Connection {
+            message: format!("Connecting aborted due '{}'", join_error),
+        }),
+        Err(_) => Err(DatabaseError::Timeout {
+            message: format!(
+                "Connecting timed out after {} seconds",
+                connection.timeouts.connect_seconds
+            ),
+        }),
+    };
+}
+
+async fn establish_orm_connection(
+    connection: &ConnectionDescription,
+    host: &str,
+    port: u16,
+) -> Result<OrmConnection, DatabaseError> {
+    let connection_result = try_establish_orm_connection(connection, host, port).await;
+
+    if connection_result.is_err() {
+        // The URL is not logged since it contains the password
+        error!(
+            "Connecting to database '{}' at '{}:{}' failed due '{}'",
+            connection.name,
+            host,
+            port,
+            connection_result.as_ref().err().unwrap()
+        );
     }
+
+    connection_result
+}
+
+// Run the CLI clients of the databases on the SSH server
+async fn get_remote_cli_connection(
+    connection: ConnectionDescription,
+    ssh_tunnel_description: &SshTunnelDescription,
+) -> Result<DbConnection, DatabaseError> {
+    let opt_sql_login_command = render_command_template(&connection);
+
+    if opt_sql_login_command.is_none() {
+        return Err(DatabaseError::InvalidInput {
+            message: String::from("Command template of the remote CLI is invalid"),
+        });
+    }
+
+    let ssh_client = setup_ssh_client(ssh_tunnel_description, &connection.timeouts).await?;
+
+    return Ok(DbConnection::SshBased(SshConnection::new(
+        ssh_client,
+        opt_sql_login_command.unwrap(),
+        connection.password,
+        connection.backend,
+        connection.timeouts.get_query_timeout(),
+    )));
+}
+
+async fn get_port_forwarded_connection(
+    connection: ConnectionDescription,
+    ssh_tunnel_description: SshTunnelDescription,
+) -> Result<DbConnection, DatabaseError> {
+    let remote_host = connection.host.clone();
+    let remote_port = connection.port;
+    let timeouts = connection.timeouts.clone();
+
+    // The forwarding has to proceed while diesel blocks the current thread
+    let spawn_result = RUNTIME
+        .spawn(async move {
+            let ssh_client = setup_ssh_client(&ssh_tunnel_description, &timeouts).await?;
+
+            return match start_port_forwarding(ssh_client, remote_host, remote_port).await {
+                Some(port_forwarding) => Ok(port_forwarding),
+                None => Err(DatabaseError::Ssh {
+                    message: String::from("Could not forward port of database"),
+                }),
+            };
+        })
+        .await;
+
+    if spawn_result.is_err() {
+        let message = format!(
+            "Setting up port forwarding failed due '{}'",
+            spawn_result.err().unwrap()
+        );
+        error!("{}", message);
+        return Err(DatabaseError::Connection { message });
+    }
+
+    let port_forwarding = spawn_result.unwrap()?;
+
+    // FIXME The certificate of the server is verified against the loopback address instead of its host name
+    if connection.tls.mode == TlsMode::VerifyFull {
+        warn!("Verifying the host name of the database is not supported for forwarded ports");
+    }
+
+    let orm_connection =
+        establish_orm_connection(&connection, "127.0.0.1", port_forwarding.get_local_port())
+            .await?;
+
+    return Ok(DbConnection::OrmBased(
+        orm_connection,
+        Some(port_forwarding),
+        None,
+    ));
+}
+
+pub async fn get_connection(
+    connection: ConnectionDescription,
+) -> Result<DbConnection, DatabaseError> {
+    if !connection.has_server() && connection.ssh_tunnel.is_some() {
+        let message = String::from("Database files can only be opened locally without SSH tunnel");
+        error!("{}", message);
+        return Err(DatabaseError::InvalidInput { message });
+    }
+
+    if connection.ssh_tunnel.is_none() {
+        return establish_orm_connection(&connection, &connection.host, connection.port)
+            .await
+            .map(|orm_connection| DbConnection::OrmBased(orm_connection, None, None));
+    }
+
+    if connection.socket_path.is_some() {
+        let message =
+            String::from("Unix sockets are only supported for connections without SSH tunnel");
+        error!("{}", message);
+        return Err(DatabaseError::InvalidInput { message });
+    }
+
+    let ssh_tunnel_description = connection.ssh_tunnel.clone().unwrap();
+
+    return match ssh_tunnel_description.mode {
+        SshTunnelMode::PortForwarding => {
+            get_port_forwarded_connection(connection, ssh_tunnel_description).await
+        }
+        SshTunnelMode::RemoteCli => {
+            get_remote_cli_connection(connection, &ssh_tunnel_description).await
+        }
+    };
 }
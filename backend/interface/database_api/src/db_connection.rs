@@ -1,45 +1,387 @@
-use database_types::connection_description::DatabaseBackend;
-use sea_query::QueryStatementWriter;
+use database_types::connection_description::{ConnectionDescription, DatabaseBackend};
+use diesel::QueryableByName;
+use log::{error, warn};
+use sea_query::{Expr, Query, QueryStatementWriter};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
 
 use crate::{
-    api::models, orm_connection::OrmConnection, sql_stringifier::SqlStringifier,
+    api::{connection_manager::RUNTIME, database_error::DatabaseError},
+    connection::try_establish_orm_connection,
+    json_field_conversion::JsonFieldConversion,
+    orm_connection::{OrmBackend, OrmConnection},
+    port_forwarding::PortForwarding,
+    sql_stringifier::SqlStringifier,
     ssh_connection::SshConnection,
 };
 
+/* Models have to be loadable by the ORM as well as from the output of the CLIs which is converted to JSON first. All
+ * models generated by diesel_ext deriving QueryableByName, Deserialize and JsonFieldConversionGenerator qualify.
+ */
+pub trait LoadableModel:
+    QueryableByName<OrmBackend> + JsonFieldConversion + DeserializeOwned + 'static
+{
+}
+
+impl<ModelType> LoadableModel for ModelType where
+    ModelType: QueryableByName<OrmBackend> + JsonFieldConversion + DeserializeOwned + 'static
+{
+}
+
+pub enum TransactionOutcome {
+    // Every statement affected at least one row
+    Committed,
+    // Statement which failed or did not affect any row
+    RolledBack {
+        statement_index: usize,
+        statement_result: Result<usize, DatabaseError>,
+    },
+}
+
+// Connection which cancels statements of another one. It reuses the SSH session or tunnel of the other connection.
+enum CancellingConnection {
+    // The cancelled connection blocks while executing. Therefore every cancellation connects anew.
+    OrmBased {
+        description: Box<ConnectionDescription>,
+        host: String,
+        port: u16,
+    },
+    // Separate CLI session on the SSH session of the cancelled connection
+    SshBased(Mutex<SshConnection>),
+}
+
+pub struct StatementCanceller {
+    // Aborts the statement which currently runs in the server session of the cancelled connection (if any)
+    cancel_statement: String,
+    connection: CancellingConnection,
+}
+
+impl StatementCanceller {
+    #[cfg_attr(
+        not(any(feature = "mysql", feature = "postgres")),
+        allow(unused_variables)
+    )]
+    fn create_cancel_statement(
+        backend: &DatabaseBackend,
+        server_session_id: i32,
+    ) -> Result<String, DatabaseError> {
+        return match backend {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => {
+                Ok(format!("SELECT pg_cancel_backend({})", server_session_id))
+            }
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => Ok(format!("KILL QUERY {}", server_session_id)),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => Err(DatabaseError::Unsupported {
+                message: String::from("SQLite databases have no server session"),
+            }),
+        };
+    }
+
+    pub async fn cancel(&self) -> Result<(), DatabaseError> {
+        return match &self.connection {
+            CancellingConnection::OrmBased {
+                description,
+                host,
+                port,
+            } => {
+                let connection_result =
+                    try_establish_orm_connection(description, host, *port).await;
+
+                if connection_result.is_err() {
+                    error!("Could not connect for cancelling the running statement");
+                    return Err(connection_result.err().unwrap());
+                }
+
+                connection_result
+                    .unwrap()
+                    .execute_sql(self.cancel_statement.clone())
+                    .map(|_| ())
+            }
+            CancellingConnection::SshBased(connection) => connection
+                .lock()
+                .await
+                .execute_sql(self.cancel_statement.clone())
+                .await
+                .map(|_| ()),
+        };
+    }
+}
+
+// Cancels statements of the ORM which run longer than the query timeout
+pub struct StatementWatchdog {
+    canceller: Arc<StatementCanceller>,
+    query_timeout: Duration,
+}
+
+impl StatementWatchdog {
+    pub fn new(canceller: Arc<StatementCanceller>, query_timeout: Duration) -> Self {
+        Self {
+            canceller,
+            query_timeout,
+        }
+    }
+
+    /* Run the statement while a separate task cancels it after the query timeout. The task runs on the runtime of the
+     * connection manager since the ORM blocks the current thread.
+     */
+    async fn watch<ResultType>(
+        opt_watchdog: &Option<StatementWatchdog>,
+        statement: impl FnOnce() -> Result<ResultType, DatabaseError>,
+    ) -> Result<ResultType, DatabaseError> {
+        if opt_watchdog.is_none() {
+            return statement();
+        }
+
+        let watchdog = opt_watchdog.as_ref().unwrap();
+        let canceller = watchdog.canceller.clone();
+        let query_timeout = watchdog.query_timeout;
+        let watchdog_task = RUNTIME.spawn(async move {
+            sleep(query_timeout).await;
+
+            warn!(
+                "Cancelling statement since it exceeded {} seconds",
+                query_timeout.as_secs()
+            );
+            let _ = canceller.cancel().await;
+        });
+        let start = Instant::now();
+
+        let statement_result = statement();
+
+        // The watchdog must not cancel following statements
+        watchdog_task.abort();
+        let _ = watchdog_task.await;
+
+        // Statements cancelled by the watchdog exceeded the query timeout
+        return match statement_result {
+            Err(DatabaseError::Cancelled { message }) if start.elapsed() >= query_timeout => {
+                Err(DatabaseError::Timeout { message })
+            }
+            statement_result => statement_result,
+        };
+    }
+}
+
 pub enum DbConnection {
-    OrmBased(OrmConnection),
+    /* The port forwarding of SSH tunnels has to live as long as the connection. The CLIs do not need any watchdog since
+     * waiting for their output times out.
+     */
+    OrmBased(
+        OrmConnection,
+        Option<PortForwarding>,
+        Option<StatementWatchdog>,
+    ),
     SshBased(SshConnection),
 }
 
 impl DbConnection {
     pub fn get_backend(&self) -> DatabaseBackend {
         return match self {
-            Self::OrmBased(connection) => connection.get_backend(),
+            Self::OrmBased(connection, _, _) => connection.get_backend(),
             Self::SshBased(connection) => connection.get_backend(),
         };
     }
 
-    pub async fn load_member<QueryType: QueryStatementWriter>(
+    // Cheap check whether the connection is still usable
+    pub async fn is_healthy(&mut self) -> bool {
+        return match self {
+            // The check is cancelled after the query timeout like any other statement
+            Self::OrmBased(connection, opt_port_forwarding, opt_watchdog) => {
+                let is_tunnel_open = opt_port_forwarding
+                    .as_ref()
+                    .is_none_or(|port_forwarding| port_forwarding.is_open());
+                is_tunnel_open
+                    && StatementWatchdog::watch(opt_watchdog, || {
+                        connection.execute_sql(String::from("SELECT 1"))
+                    })
+                    .await
+                    .is_ok()
+            }
+            // Avoid logging in to the database again via SSH
+            Self::SshBased(connection) => connection.is_session_open(),
+        };
+    }
+
+    // Load all rows of the query as the given model e.g. "load::<models::Member, _>(query)"
+    pub async fn load<ObjectType, QueryType>(
+        &mut self,
+        sql_query: QueryType,
+    ) -> Result<Vec<ObjectType>, DatabaseError>
+    where
+        ObjectType: LoadableModel,
+        QueryType: QueryStatementWriter,
+        DatabaseBackend: SqlStringifier<QueryType>,
+    {
+        return match self {
+            Self::OrmBased(connection, _, opt_watchdog) => {
+                StatementWatchdog::watch(opt_watchdog, || {
+                    connection.load::<ObjectType, QueryType>(sql_query)
+                })
+                .await
+            }
+            Self::SshBased(connection) => connection.load::<ObjectType, QueryType>(sql_query).await,
+        };
+    }
+
+    // Load the integer column named "value" of all rows
+    pub async fn load_integers<QueryType>(
         &mut self,
         sql_query: QueryType,
-    ) -> Option<Vec<models::Member>>
+    ) -> Result<Vec<i32>, DatabaseError>
     where
         QueryType: QueryStatementWriter,
         DatabaseBackend: SqlStringifier<QueryType>,
     {
         return match self {
-            Self::OrmBased(connection) => connection.load_member(sql_query),
-            Self::SshBased(connection) => connection.load_member(sql_query).await,
+            Self::OrmBased(connection, _, opt_watchdog) => {
+                StatementWatchdog::watch(opt_watchdog, || connection.load_integers(sql_query)).await
+            }
+            Self::SshBased(connection) => connection.load_integers(sql_query).await,
         };
     }
 
-    pub async fn execute_sql<QueryType>(&mut self, sql_query: QueryType) -> Option<usize>
+    pub async fn execute_sql<QueryType>(
+        &mut self,
+        sql_query: QueryType,
+    ) -> Result<usize, DatabaseError>
     where
         DatabaseBackend: SqlStringifier<QueryType>,
     {
         return match self {
-            Self::OrmBased(connection) => connection.execute_sql(sql_query),
+            Self::OrmBased(connection, _, opt_watchdog) => {
+                StatementWatchdog::watch(opt_watchdog, || connection.execute_sql(sql_query)).await
+            }
             Self::SshBased(connection) => connection.execute_sql(sql_query).await,
         };
     }
+
+    // Execute all statements or none of them. Statements which do not affect any row roll back the transaction as well.
+    pub async fn execute_transaction<QueryType>(
+        &mut self,
+        sql_queries: Vec<QueryType>,
+    ) -> Result<TransactionOutcome, DatabaseError>
+    where
+        DatabaseBackend: SqlStringifier<QueryType>,
+    {
+        self.begin_transaction().await?;
+
+        for (statement_index, sql_query) in sql_queries.into_iter().enumerate() {
+            let statement_result = self.execute_sql(sql_query).await;
+
+            if !matches!(statement_result, Ok(num_affected_rows) if num_affected_rows > 0) {
+                warn!(
+                    "Rolling back transaction since statement {} failed or had no effect",
+                    statement_index
+                );
+
+                self.rollback_transaction().await?;
+
+                return Ok(TransactionOutcome::RolledBack {
+                    statement_index,
+                    statement_result,
+                });
+            }
+        }
+
+        self.commit_transaction().await?;
+
+        Ok(TransactionOutcome::Committed)
+    }
+
+    // Statements until commit_transaction() or rollback_transaction() are applied all at once or not at all
+    pub async fn begin_transaction(&mut self) -> Result<(), DatabaseError> {
+        return match self {
+            Self::OrmBased(connection, _, opt_watchdog) => {
+                StatementWatchdog::watch(opt_watchdog, || connection.begin_transaction()).await
+            }
+            Self::SshBased(connection) => connection.execute_transaction_control("BEGIN").await,
+        };
+    }
+
+    pub async fn commit_transaction(&mut self) -> Result<(), DatabaseError> {
+        return match self {
+            Self::OrmBased(connection, _, opt_watchdog) => {
+                StatementWatchdog::watch(opt_watchdog, || connection.commit_transaction()).await
+            }
+            Self::SshBased(connection) => connection.execute_transaction_control("COMMIT").await,
+        };
+    }
+
+    pub async fn rollback_transaction(&mut self) -> Result<(), DatabaseError> {
+        return match self {
+            Self::OrmBased(connection, _, opt_watchdog) => {
+                StatementWatchdog::watch(opt_watchdog, || connection.rollback_transaction()).await
+            }
+            Self::SshBased(connection) => connection.execute_transaction_control("ROLLBACK").await,
+        };
+    }
+
+    // Identifier of the session on the database server which executes the statements of this connection
+    pub async fn get_server_session_id(&mut self) -> Result<i32, DatabaseError> {
+        let session_id_function: Result<&'static str, DatabaseError> = match self.get_backend() {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => Ok("pg_backend_pid()"),
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => Ok("CONNECTION_ID()"),
+            // NOTE 2026-10-18: diesel does not expose sqlite3_interrupt() hence statements on SQLite cannot be cancelled
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => Err(DatabaseError::Unsupported {
+                message: String::from("SQLite databases have no server session"),
+            }),
+        };
+
+        let select_statement = Query::select()
+            .expr_as(Expr::cust(session_id_function?), "value")
+            .to_owned();
+
+        return match self.load_integers(select_statement).await?.first() {
+            Some(server_session_id) => Ok(*server_session_id),
+            None => Err(DatabaseError::UnexpectedResult {
+                message: String::from("Server did not report a session ID"),
+            }),
+        };
+    }
+
+    // Only statements of ORM based connections are cancelled by the watchdog
+    pub fn set_statement_watchdog(&mut self, watchdog: StatementWatchdog) {
+        if let Self::OrmBased(_, _, opt_watchdog) = self {
+            *opt_watchdog = Some(watchdog);
+        }
+    }
+
+    // Prepare cancelling statements of this connection while it is busy executing them
+    pub async fn create_statement_canceller(
+        &mut self,
+        description: &ConnectionDescription,
+    ) -> Result<StatementCanceller, DatabaseError> {
+        let server_session_id = self.get_server_session_id().await?;
+        let cancel_statement =
+            StatementCanceller::create_cancel_statement(&self.get_backend(), server_session_id)?;
+
+        let connection = match self {
+            Self::OrmBased(_, Some(port_forwarding), _) => CancellingConnection::OrmBased {
+                description: Box::new(description.clone()),
+                host: String::from("127.0.0.1"),
+                port: port_forwarding.get_local_port(),
+            },
+            Self::OrmBased(_, None, _) => CancellingConnection::OrmBased {
+                description: Box::new(description.clone()),
+                host: description.host.clone(),
+                port: description.port,
+            },
+            Self::SshBased(connection) => {
+                CancellingConnection::SshBased(Mutex::new(connection.create_sibling()))
+            }
+        };
+
+        Ok(StatementCanceller {
+            cancel_statement,
+            connection,
+        })
+    }
 }
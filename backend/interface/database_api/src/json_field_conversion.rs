@@ -1,3 +1,46 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    Text,
+    Integer,
+    Boolean,
+    Double,
+    Date,
+    DateTime,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldTypeInfo {
+    pub field_type: FieldType,
+    pub nullable: bool,
+}
+
+// The generated function receives None for NULL values
+pub type JsonValueGenerator = Box<dyn Fn(Option<&str>) -> serde_json::Value>;
+
 pub trait JsonFieldConversion {
-    fn get_json_value_generator(field_name: &str) -> Box<dyn Fn(&str) -> serde_json::Value>;
+    fn get_json_value_generator(field_name: &str) -> JsonValueGenerator;
+
+    // Return None if there is no such field
+    fn get_field_type(field_name: &str) -> Option<FieldTypeInfo>;
+
+    fn get_field_names() -> Vec<&'static str>;
+
+    // Return None if there is no such field
+    fn get_column_name(field_name: &str) -> Option<&'static str>;
+}
+
+/* Structures representing data of databases are generated by Diesel. Due to lack of support for non-ASCII
+ * characters in Rust member names (really or is it just Diesel?) non-ASCII characters are replaced by underscores.
+ * In contrast command line tools return results containing column names including all special characters. To make
+ * the structure member match the column names returned by command line tools, the JSON keys representing column
+ * names (in the JSON object representing the command line output) have to be sanitized the same way like Diesel
+ * does.
+ * FIXME Is the sanitization function for Diesel member accessible directly?
+ */
+pub fn get_field_name(column_name: &str) -> String {
+    // FIXME Verify in which cases it is true that non ASCII characters are replaced by underscore
+    column_name
+        .chars()
+        .map(|c| if c.is_ascii() { c } else { '_' })
+        .collect()
 }
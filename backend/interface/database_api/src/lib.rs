@@ -1,13 +1,17 @@
 pub mod api;
 
+mod cli_output;
 mod connection;
 mod db_connection;
 mod frb_generated;
 mod json_field_conversion;
+mod migration;
 mod orm_connection;
+mod port_forwarding;
 mod schema;
-mod ssh_connection;
 mod sql_stringifier;
+mod ssh_connection;
+mod value_conversion;
 
 #[cfg(test)]
 mod test_database_common;
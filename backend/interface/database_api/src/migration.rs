@@ -0,0 +1,160 @@
+use database_types::connection_description::DatabaseBackend;
+
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    up_sql: &'static str,
+    // Scripts replacing up_sql for DB backends whose syntax differs
+    backend_up_sqls: &'static [(DatabaseBackend, &'static str)],
+}
+
+// Ordered by version. Every migration registers its version in the table schema_version itself which allows applying
+// the same scripts via the diesel CLI during development.
+pub const MIGRATIONS: [Migration; 3] = [
+    Migration {
+        version: 1,
+        description: "Create member table",
+        up_sql: include_str!("../migrations/2026-10-18-000001_create_member/up.sql"),
+        backend_up_sqls: &[],
+    },
+    Migration {
+        version: 2,
+        description: "Create member exit table",
+        up_sql: include_str!("../migrations/2026-10-18-000002_create_member_exit/up.sql"),
+        backend_up_sqls: &[],
+    },
+    Migration {
+        version: 3,
+        description: "Create member change table",
+        up_sql: include_str!("../migrations/2026-10-18-000003_create_member_change/up.sql"),
+        backend_up_sqls: &[
+            #[cfg(feature = "mysql")]
+            (
+                DatabaseBackend::MySql,
+                include_str!("../migrations/2026-10-18-000003_create_member_change/up.mysql.sql"),
+            ),
+            #[cfg(feature = "sqlite")]
+            (
+                DatabaseBackend::Sqlite,
+                include_str!("../migrations/2026-10-18-000003_create_member_change/up.sqlite.sql"),
+            ),
+        ],
+    },
+];
+
+pub fn get_latest_version() -> i32 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+// Not all kinds of connections support executing multiple statements at once
+fn split_sql_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::<String>::new();
+    let mut statement = String::new();
+    let mut opt_quote = None::<char>;
+    let mut characters = script.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        // Escaped quotes within literals are doubled which closes and reopens the literal
+        if let Some(quote) = opt_quote {
+            if character == quote {
+                opt_quote = None;
+            }
+            statement.push(character);
+            continue;
+        }
+
+        match character {
+            '\'' | '"' | '`' => {
+                opt_quote = Some(character);
+                statement.push(character);
+            }
+            '-' if characters.peek() == Some(&'-') => {
+                while characters.next_if(|next| *next != '\n').is_some() {}
+            }
+            '/' if characters.peek() == Some(&'*') => {
+                characters.next();
+                while let Some(next) = characters.next() {
+                    if next == '*' && characters.next_if_eq(&'/').is_some() {
+                        break;
+                    }
+                }
+            }
+            ';' => statements.push(std::mem::take(&mut statement)),
+            _ => statement.push(character),
+        }
+    }
+
+    statements.push(statement);
+
+    statements
+        .iter()
+        .map(|statement| statement.trim())
+        .filter(|statement| !statement.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+impl Migration {
+    pub fn get_up_statements(&self, backend: &DatabaseBackend) -> Vec<String> {
+        let up_sql = self
+            .backend_up_sqls
+            .iter()
+            .find(|(up_sql_backend, _)| up_sql_backend == backend)
+            .map_or(self.up_sql, |(_, backend_up_sql)| backend_up_sql);
+
+        split_sql_statements(up_sql)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    const ENABLED_BACKENDS: &[DatabaseBackend] = &[
+        #[cfg(feature = "mysql")]
+        DatabaseBackend::MySql,
+        #[cfg(feature = "postgres")]
+        DatabaseBackend::PostgreSql,
+        #[cfg(feature = "sqlite")]
+        DatabaseBackend::Sqlite,
+    ];
+
+    #[test]
+    fn test_migrations() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_that!(migration.version).is_equal_to(index as i32 + 1);
+
+            for backend in ENABLED_BACKENDS {
+                let statements = migration.get_up_statements(backend);
+                assert_that!(statements.len()).is_greater_than(1);
+                assert_that!(statements.last().unwrap().as_str()).contains(
+                    format!(
+                        "VALUES ({}, '{}')",
+                        migration.version, migration.description
+                    )
+                    .as_str(),
+                );
+                assert_that!(statements.iter().any(|statement| statement.contains("--")))
+                    .is_false();
+            }
+        }
+
+        assert_that!(get_latest_version()).is_equal_to(MIGRATIONS.len() as i32);
+    }
+
+    #[test]
+    fn test_split_sql_statements() {
+        let script = "-- Leading comment; not a statement\n\
+            CREATE TABLE a (b varchar(3) DEFAULT ';', c int); -- Trailing comment\n\
+            /* Block comment; */ INSERT INTO a (b) VALUES ('it''s; --');\n\
+            SELECT \"semi;colon\" FROM a";
+
+        assert_that!(split_sql_statements(script)).is_equal_to(vec![
+            String::from("CREATE TABLE a (b varchar(3) DEFAULT ';', c int)"),
+            String::from("INSERT INTO a (b) VALUES ('it''s; --')"),
+            String::from("SELECT \"semi;colon\" FROM a"),
+        ]);
+    }
+}
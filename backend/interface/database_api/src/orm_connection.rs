@@ -3,10 +3,13 @@ use database_types::connection_description::DatabaseBackend;
 use diesel::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::PgConnection;
-use diesel::{MultiConnection, RunQueryDsl};
+#[cfg(feature = "sqlite")]
+use diesel::SqliteConnection;
+use diesel::connection::{Connection, TransactionManager};
+use diesel::{MultiConnection, QueryResult, QueryableByName, RunQueryDsl};
 use sea_query::QueryStatementWriter;
 
-use crate::{api::models, sql_stringifier::SqlStringifier};
+use crate::{api::database_error::DatabaseError, sql_stringifier::SqlStringifier};
 use log::error;
 
 #[derive(MultiConnection)]
@@ -15,6 +18,19 @@ pub enum OrmConnection {
     MySql(MysqlConnection),
     #[cfg(feature = "postgres")]
     PostgreSql(PgConnection),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteConnection),
+}
+
+type OrmTransactionManager = <OrmConnection as Connection>::TransactionManager;
+
+// Backend of the ORM which dispatches to the backend of the actual connection
+pub type OrmBackend = <OrmConnection as Connection>::Backend;
+
+#[derive(QueryableByName)]
+struct IntegerRow {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    value: i32,
 }
 
 impl OrmConnection {
@@ -24,31 +40,61 @@ impl OrmConnection {
             Self::PostgreSql(_) => DatabaseBackend::PostgreSql,
             #[cfg(feature = "mysql")]
             Self::MySql(_) => DatabaseBackend::MySql,
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(_) => DatabaseBackend::Sqlite,
+        };
+    }
+
+    pub fn load<ObjectType, QueryType>(
+        &mut self,
+        sql_query: QueryType,
+    ) -> Result<Vec<ObjectType>, DatabaseError>
+    where
+        ObjectType: QueryableByName<OrmBackend> + 'static,
+        QueryType: QueryStatementWriter,
+        DatabaseBackend: SqlStringifier<QueryType>,
+    {
+        let sql_query_string = self.get_backend().to_sql_string(sql_query);
+
+        let query_result = diesel::sql_query(&sql_query_string).load::<ObjectType>(self);
+
+        return match query_result {
+            Ok(result) => Ok(result),
+            Err(error) => {
+                error!(
+                    "Executing query '{}' failed due '{}'",
+                    sql_query_string, error
+                );
+                Err(DatabaseError::from(error))
+            }
         };
     }
 
-    pub fn load_member<QueryType>(&mut self, sql_query: QueryType) -> Option<Vec<models::Member>>
+    pub fn load_integers<QueryType>(
+        &mut self,
+        sql_query: QueryType,
+    ) -> Result<Vec<i32>, DatabaseError>
     where
         QueryType: QueryStatementWriter,
         DatabaseBackend: SqlStringifier<QueryType>,
     {
         let sql_query_string = self.get_backend().to_sql_string(sql_query);
 
-        let query_result = diesel::sql_query(&sql_query_string).load::<models::Member>(self);
+        let query_result = diesel::sql_query(&sql_query_string).load::<IntegerRow>(self);
 
         return match query_result {
-            Ok(result) => Some(result),
+            Ok(result) => Ok(result.into_iter().map(|row| row.value).collect()),
             Err(error) => {
                 error!(
                     "Executing query '{}' failed due '{}'",
                     sql_query_string, error
                 );
-                return None;
+                Err(DatabaseError::from(error))
             }
         };
     }
 
-    pub fn execute_sql<QueryType>(&mut self, sql_query: QueryType) -> Option<usize>
+    pub fn execute_sql<QueryType>(&mut self, sql_query: QueryType) -> Result<usize, DatabaseError>
     where
         DatabaseBackend: SqlStringifier<QueryType>,
     {
@@ -57,14 +103,50 @@ impl OrmConnection {
         let query_result = diesel::sql_query(&sql_query_string).execute(self);
 
         return match query_result {
-            Ok(result) => Some(result),
+            Ok(result) => Ok(result),
             Err(error) => {
                 error!(
                     "Executing query '{}' failed due '{}'",
                     sql_query_string, error
                 );
-                return None;
+                Err(DatabaseError::from(error))
+            }
+        };
+    }
+
+    fn check_transaction_result(
+        result: QueryResult<()>,
+        action: &str,
+    ) -> Result<(), DatabaseError> {
+        return match result {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                error!("Could not {} transaction due '{}'", action, error);
+                Err(DatabaseError::from(error))
             }
         };
     }
+
+    pub fn begin_transaction(&mut self) -> Result<(), DatabaseError> {
+        Self::check_transaction_result(
+            <OrmTransactionManager as TransactionManager<OrmConnection>>::begin_transaction(self),
+            "begin",
+        )
+    }
+
+    pub fn commit_transaction(&mut self) -> Result<(), DatabaseError> {
+        Self::check_transaction_result(
+            <OrmTransactionManager as TransactionManager<OrmConnection>>::commit_transaction(self),
+            "commit",
+        )
+    }
+
+    pub fn rollback_transaction(&mut self) -> Result<(), DatabaseError> {
+        Self::check_transaction_result(
+            <OrmTransactionManager as TransactionManager<OrmConnection>>::rollback_transaction(
+                self,
+            ),
+            "roll back",
+        )
+    }
 }
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use log::{error, info, warn};
+use russh::client;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::connection::SshClient;
+
+// Exposes a port of the database host on a local loopback port via a direct-tcpip channel
+pub struct PortForwarding {
+    local_port: u16,
+    session: Arc<client::Handle<SshClient>>,
+    listener_task: JoinHandle<()>,
+}
+
+impl PortForwarding {
+    pub fn get_local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.session.is_closed() && !self.listener_task.is_finished()
+    }
+}
+
+impl Drop for PortForwarding {
+    fn drop(&mut self) {
+        self.listener_task.abort();
+    }
+}
+
+async fn forward_stream(
+    session: Arc<client::Handle<SshClient>>,
+    mut local_stream: TcpStream,
+    remote_host: String,
+    remote_port: u16,
+) {
+    let originator_port = match local_stream.peer_addr() {
+        Ok(address) => address.port(),
+        Err(_) => 0,
+    };
+
+    let channel_result = session
+        .channel_open_direct_tcpip(
+            remote_host.clone(),
+            remote_port as u32,
+            "127.0.0.1",
+            originator_port as u32,
+        )
+        .await;
+
+    if channel_result.is_err() {
+        error!(
+            "Opening direct-tcpip channel to '{}:{}' failed due '{}'",
+            remote_host,
+            remote_port,
+            channel_result.err().unwrap()
+        );
+        return;
+    }
+
+    let mut channel_stream = channel_result.unwrap().into_stream();
+
+    if let Err(error) = tokio::io::copy_bidirectional(&mut local_stream, &mut channel_stream).await
+    {
+        // NOTE Database clients commonly just close the socket when disconnecting
+        warn!(
+            "Forwarding to '{}:{}' ended due '{}'",
+            remote_host, remote_port, error
+        );
+    }
+}
+
+// NOTE Has to be called within a runtime which keeps running while the connection is used
+pub async fn start_port_forwarding(
+    session: client::Handle<SshClient>,
+    remote_host: String,
+    remote_port: u16,
+) -> Option<PortForwarding> {
+    let listener_result = TcpListener::bind(("127.0.0.1", 0)).await;
+
+    if listener_result.is_err() {
+        error!(
+            "Binding local port for forwarding failed due '{}'",
+            listener_result.err().unwrap()
+        );
+        return None;
+    }
+
+    let listener = listener_result.unwrap();
+    let local_address_result = listener.local_addr();
+
+    if local_address_result.is_err() {
+        error!(
+            "Could not determine local port for forwarding due '{}'",
+            local_address_result.err().unwrap()
+        );
+        return None;
+    }
+
+    let local_port = local_address_result.unwrap().port();
+    let session = Arc::new(session);
+    let listener_session = session.clone();
+
+    info!(
+        "Forwarding local port '{}' to '{}:{}'",
+        local_port, remote_host, remote_port
+    );
+
+    let listener_task = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((local_stream, _)) => {
+                    tokio::spawn(forward_stream(
+                        listener_session.clone(),
+                        local_stream,
+                        remote_host.clone(),
+                        remote_port,
+                    ));
+                }
+                Err(error) => {
+                    error!("Accepting forwarded connection failed due '{}'", error);
+                    return;
+                }
+            }
+        }
+    });
+
+    Some(PortForwarding {
+        local_port,
+        session,
+        listener_task,
+    })
+}
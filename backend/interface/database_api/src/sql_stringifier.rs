@@ -3,7 +3,11 @@ use database_types::connection_description::DatabaseBackend;
 use sea_query::MysqlQueryBuilder;
 #[cfg(feature = "postgres")]
 use sea_query::PostgresQueryBuilder;
-use sea_query::{SelectStatement, TableCreateStatement, UpdateStatement};
+#[cfg(feature = "sqlite")]
+use sea_query::SqliteQueryBuilder;
+use sea_query::{
+    DeleteStatement, InsertStatement, SelectStatement, TableCreateStatement, UpdateStatement,
+};
 
 pub trait SqlStringifier<QueryType> {
     fn to_sql_string(&self, query: QueryType) -> String;
@@ -16,6 +20,8 @@ impl SqlStringifier<SelectStatement> for DatabaseBackend {
             DatabaseBackend::PostgreSql => query.to_string(PostgresQueryBuilder),
             #[cfg(feature = "mysql")]
             DatabaseBackend::MySql => query.to_string(MysqlQueryBuilder),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => query.to_string(SqliteQueryBuilder),
         }
     }
 }
@@ -27,6 +33,8 @@ impl SqlStringifier<TableCreateStatement> for DatabaseBackend {
             DatabaseBackend::PostgreSql => query.to_string(PostgresQueryBuilder),
             #[cfg(feature = "mysql")]
             DatabaseBackend::MySql => query.to_string(MysqlQueryBuilder),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => query.to_string(SqliteQueryBuilder),
         }
     }
 }
@@ -38,6 +46,41 @@ impl SqlStringifier<UpdateStatement> for DatabaseBackend {
             DatabaseBackend::PostgreSql => query.to_string(PostgresQueryBuilder),
             #[cfg(feature = "mysql")]
             DatabaseBackend::MySql => query.to_string(MysqlQueryBuilder),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => query.to_string(SqliteQueryBuilder),
         }
     }
 }
+
+impl SqlStringifier<InsertStatement> for DatabaseBackend {
+    fn to_sql_string(&self, query: InsertStatement) -> String {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => query.to_string(PostgresQueryBuilder),
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => query.to_string(MysqlQueryBuilder),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => query.to_string(SqliteQueryBuilder),
+        }
+    }
+}
+
+impl SqlStringifier<DeleteStatement> for DatabaseBackend {
+    fn to_sql_string(&self, query: DeleteStatement) -> String {
+        match self {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => query.to_string(PostgresQueryBuilder),
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => query.to_string(MysqlQueryBuilder),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => query.to_string(SqliteQueryBuilder),
+        }
+    }
+}
+
+// Plain SQL e.g. of migrations has to be valid for the backend already
+impl SqlStringifier<String> for DatabaseBackend {
+    fn to_sql_string(&self, query: String) -> String {
+        query
+    }
+}
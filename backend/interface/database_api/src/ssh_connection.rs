@@ -1,110 +1,332 @@
-use crate::api::models;
+use crate::api::database_error::DatabaseError;
+use crate::cli_output::{CliTable, parse_cli_table, wrap_cli_query};
+use crate::connection::SshClient;
 use crate::json_field_conversion::JsonFieldConversion;
+use crate::json_field_conversion::JsonValueGenerator;
+use crate::json_field_conversion::get_field_name;
 use crate::sql_stringifier::SqlStringifier;
 use database_types::connection_description::DatabaseBackend;
 use log::error;
 use log::warn;
 use russh::Channel;
 use russh::ChannelMsg;
+use russh::client;
 use russh::client::Msg;
 use sea_query::QueryStatementWriter;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+
+// Column names of the row printed by the sentinel query of mysql
+#[cfg(feature = "mysql")]
+const MYSQL_SENTINEL_HEADER: &str = "sentinel\terror_count\trow_count";
+
+// Interactive CLI process of the database which runs all statements of a connection
+struct CliSession {
+    channel: Channel<Msg>,
+    // Output which does not form a complete line yet
+    output_buffer: Vec<u8>,
+    num_sentinels: usize,
+}
+
+struct StatementResult {
+    output_lines: Vec<String>,
+    has_failed: bool,
+    // Number of rows the statement returned or affected
+    opt_row_count: Option<usize>,
+}
 
 pub struct SshConnection {
-    pub channel: Channel<Msg>,
+    // Shared with siblings
+    session: Arc<client::Handle<SshClient>>,
     // FIXME Type of shell required?
-    pub sql_login_command: String,
-    pub password: String,
-    pub backend: DatabaseBackend,
+    sql_login_command: String,
+    password: String,
+    backend: DatabaseBackend,
+    // Maximum time without any output of the CLI while waiting for the result of a statement
+    query_timeout: Duration,
+    // Started on the first statement
+    opt_cli_session: Option<CliSession>,
 }
 
 struct FieldGenerator {
     field_name: String,
+    #[allow(dead_code)]
     column_name: String,
     column_index: usize,
-    to_json: Box<dyn Fn(&str) -> serde_json::Value>,
+    to_json: JsonValueGenerator,
 }
 
 impl SshConnection {
+    pub fn new(
+        session: client::Handle<SshClient>,
+        sql_login_command: String,
+        password: String,
+        backend: DatabaseBackend,
+        query_timeout: Duration,
+    ) -> Self {
+        Self {
+            session: Arc::new(session),
+            sql_login_command,
+            password,
+            backend,
+            query_timeout,
+            opt_cli_session: None,
+        }
+    }
+
     pub fn get_backend(&self) -> DatabaseBackend {
         self.backend.clone()
     }
 
-    fn split_sql_cells(output: Vec<String>) -> Vec<Vec<String>> {
-        output
-            .iter()
-            .map(|line| line.split('\t').map(ToOwned::to_owned).collect())
-            .collect()
+    // Connection which runs its statements in a separate CLI session on the same SSH session
+    pub fn create_sibling(&self) -> Self {
+        Self {
+            session: self.session.clone(),
+            sql_login_command: self.sql_login_command.clone(),
+            password: self.password.clone(),
+            backend: self.backend.clone(),
+            query_timeout: self.query_timeout,
+            opt_cli_session: None,
+        }
     }
 
-    async fn read_channel_output(&mut self) -> Vec<String> {
-        let mut opt_exit_status = None;
-        let mut stdout_buffer = Vec::new();
-        let mut stderr_buffer = Vec::new();
-        loop {
-            let Some(message) = self.channel.wait().await else {
-                break;
-            };
-            match message {
-                ChannelMsg::Data { data } => stdout_buffer.extend_from_slice(&data),
-                ChannelMsg::ExtendedData { data, ext: _ } => stderr_buffer.extend_from_slice(&data),
-                ChannelMsg::ExitStatus { exit_status } => opt_exit_status = Some(exit_status),
-                _ => {}
-            }
+    pub fn is_session_open(&self) -> bool {
+        !self.session.is_closed()
+    }
+
+    // Every statement is followed by a command printing a line which starts with the sentinel and ends with the status
+    #[cfg_attr(
+        not(any(feature = "mysql", feature = "postgres")),
+        allow(unused_variables)
+    )]
+    fn create_sentinel_command(backend: &DatabaseBackend, sentinel: &str) -> String {
+        match backend {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => format!("\\echo {} :ERROR :ROW_COUNT\n", sentinel),
+            // ROW_COUNT() yields -1 for failed statements as well as for queries
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => format!(
+                "SELECT '{}' AS sentinel, @@error_count AS error_count, ROW_COUNT() AS row_count;\n",
+                sentinel
+            ),
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => unreachable!("SQLite databases are not accessed via SSH"),
         }
+    }
+
+    // Interpret the status printed after the sentinel and remove output belonging to the sentinel command itself
+    #[cfg_attr(not(feature = "mysql"), allow(unused_variables, clippy::ptr_arg))]
+    fn parse_sentinel_line(
+        backend: DatabaseBackend,
+        output_lines: &mut Vec<String>,
+        sentinel_line: &str,
+    ) -> (bool, Option<usize>) {
+        let status: Vec<&str> = sentinel_line.split_whitespace().skip(1).collect();
 
-        let close_result = self.channel.close().await;
-        match close_result {
-            Ok(_) => {}
-            Err(error) => error!("Closing SSH channel failed due '{}'", error),
+        match backend {
+            #[cfg(feature = "postgres")]
+            DatabaseBackend::PostgreSql => (
+                status.first() != Some(&"false"),
+                status
+                    .get(1)
+                    .and_then(|row_count| row_count.parse::<usize>().ok()),
+            ),
+            #[cfg(feature = "mysql")]
+            DatabaseBackend::MySql => {
+                if output_lines.last().map(String::as_str) == Some(MYSQL_SENTINEL_HEADER) {
+                    output_lines.pop();
+                }
+
+                (
+                    status.first() != Some(&"0"),
+                    status
+                        .get(1)
+                        .and_then(|row_count| row_count.parse::<usize>().ok()),
+                )
+            }
+            #[cfg(feature = "sqlite")]
+            DatabaseBackend::Sqlite => unreachable!("SQLite databases are not accessed via SSH"),
         }
+    }
+
+    async fn write_to_cli_session(
+        cli_session: &mut CliSession,
+        input: String,
+    ) -> Result<(), DatabaseError> {
+        let write_result = cli_session.channel.data(input.as_bytes()).await;
 
-        if opt_exit_status.is_none() {
-            warn!(
-                "Command '{}' finished without exit code",
-                self.sql_login_command
+        if write_result.is_err() {
+            let message = format!(
+                "Writing to CLI session failed due '{}'",
+                write_result.err().unwrap()
             );
+            error!("{}", message);
+            return Err(DatabaseError::Ssh { message });
         }
-        let exit_status = opt_exit_status.unwrap();
-        if exit_status != 0 {
-            error!(
-                "Command '{}' finished with exit code '{}'",
-                self.sql_login_command, exit_status
+
+        Ok(())
+    }
+
+    fn take_line(output_buffer: &mut Vec<u8>) -> Option<String> {
+        let newline_index = output_buffer.iter().position(|byte| *byte == b'\n')?;
+        let line_bytes: Vec<u8> = output_buffer.drain(..=newline_index).collect();
+
+        Some(
+            String::from_utf8_lossy(&line_bytes)
+                .trim_end_matches(['\r', '\n'])
+                .to_owned(),
+        )
+    }
+
+    // Return the output lines preceding the line starting with the sentinel as well as the sentinel line itself
+    async fn read_until_sentinel(
+        cli_session: &mut CliSession,
+        sentinel: &str,
+        query_timeout: Duration,
+    ) -> Result<(Vec<String>, String), DatabaseError> {
+        let mut output_lines = vec![];
+
+        loop {
+            while let Some(line) = Self::take_line(&mut cli_session.output_buffer) {
+                if line.starts_with(sentinel) {
+                    return Ok((output_lines, line));
+                }
+
+                output_lines.push(line);
+            }
+
+            let timeout_result = timeout(query_timeout, cli_session.channel.wait()).await;
+
+            if timeout_result.is_err() {
+                let message = format!(
+                    "CLI session did not print sentinel '{}' within {} seconds",
+                    sentinel,
+                    query_timeout.as_secs()
+                );
+                error!("{}", message);
+                return Err(DatabaseError::Timeout { message });
+            }
+
+            match timeout_result.unwrap() {
+                Some(ChannelMsg::Data { data }) => {
+                    cli_session.output_buffer.extend_from_slice(&data)
+                }
+                Some(ChannelMsg::ExtendedData { data, ext: _ }) => warn!(
+                    "CLI session yielded stderr: '{}'",
+                    String::from_utf8_lossy(&data)
+                ),
+                Some(ChannelMsg::ExitStatus { exit_status }) => {
+                    let message = format!("CLI session exited with exit code '{}'", exit_status);
+                    error!("{}", message);
+                    return Err(DatabaseError::Connection { message });
+                }
+                Some(_) => {}
+                None => {
+                    let message =
+                        format!("CLI session ended before printing sentinel '{}'", sentinel);
+                    error!("{}", message);
+                    return Err(DatabaseError::Connection { message });
+                }
+            }
+        }
+    }
+
+    fn create_sentinel(cli_session: &mut CliSession) -> String {
+        cli_session.num_sentinels += 1;
+        format!("__green3neo_sentinel_{}__", cli_session.num_sentinels)
+    }
+
+    async fn start_cli_session(&self) -> Result<CliSession, DatabaseError> {
+        let opt_channel = self.session.channel_open_session().await;
+
+        if opt_channel.is_err() {
+            let message = format!(
+                "Could not open SSH channel due '{}'",
+                opt_channel.err().unwrap()
             );
+            error!("{}", message);
+            return Err(DatabaseError::Ssh { message });
         }
 
-        if !stderr_buffer.is_empty() {
-            warn!(
-                "Command '{}' yielded stderr: '{}'",
-                self.sql_login_command,
-                String::from_utf8_lossy(&stderr_buffer)
+        let mut cli_session = CliSession {
+            channel: opt_channel.unwrap(),
+            output_buffer: vec![],
+            num_sentinels: 0,
+        };
+
+        let sql_login_result = cli_session
+            .channel
+            .exec(true, self.sql_login_command.as_bytes())
+            .await;
+
+        if sql_login_result.is_err() {
+            let message = format!(
+                "Could not log in to database due '{}'",
+                sql_login_result.err().unwrap()
             );
+            error!("{}", message);
+            return Err(DatabaseError::Ssh { message });
         }
 
-        return String::from_utf8_lossy(&stdout_buffer)
-            .lines()
-            .map(|line| line.to_owned())
-            .collect();
+        // Skip everything printed while logging in
+        let sentinel = Self::create_sentinel(&mut cli_session);
+        let login_input = format!(
+            "{}\n{}",
+            self.password,
+            Self::create_sentinel_command(&self.backend, &sentinel)
+        );
+
+        Self::write_to_cli_session(&mut cli_session, login_input).await?;
+        Self::read_until_sentinel(&mut cli_session, &sentinel, self.query_timeout).await?;
+
+        Ok(cli_session)
     }
 
-    /* Structures representing data of databases are generated by Diesel. Due to lack of support for non-ASCII
-     * characters in Rust member names (really or is it just Diesel?) non-ASCII characters are replaced by underscores.
-     * In contrast command line tools return results containing column names including all special characters. To make
-     * the structure member match the column names returned by command line tools, the JSON keys representing column
-     * names (in the JSON object representing the command line output) have to be sanitized the same way like Diesel
-     * does.
-     * FIXME Is the sanitization function for Diesel member accessible directly?
-     */
-    fn get_field_name(column_name: &str) -> String {
-        // FIXME Verify in which cases it is true that non ASCII characters are replaced by underscore
-        column_name
-            .chars()
-            .map(|c| if c.is_ascii() { c } else { '_' })
-            .collect()
+    async fn run_statement(&mut self, sql_query: String) -> Result<StatementResult, DatabaseError> {
+        if self.opt_cli_session.is_none() {
+            self.opt_cli_session = Some(self.start_cli_session().await?);
+        }
+
+        let cli_session = self.opt_cli_session.as_mut().unwrap();
+        let sentinel = Self::create_sentinel(cli_session);
+        let statement_input = format!(
+            "{};\n{}",
+            sql_query,
+            Self::create_sentinel_command(&self.backend, &sentinel)
+        );
+
+        let output_result = match Self::write_to_cli_session(cli_session, statement_input).await {
+            Ok(_) => Self::read_until_sentinel(cli_session, &sentinel, self.query_timeout).await,
+            Err(error) => Err(error),
+        };
+
+        if output_result.is_err() {
+            // The output of the session is out of sync. Therefore the next statement starts a new one.
+            self.opt_cli_session = None;
+            return Err(output_result.err().unwrap());
+        }
+
+        let (mut output_lines, sentinel_line) = output_result.unwrap();
+        let (has_failed, opt_row_count) =
+            Self::parse_sentinel_line(self.get_backend(), &mut output_lines, &sentinel_line);
+
+        if has_failed {
+            error!(
+                "Statement '{}' failed with output '{:?}'",
+                sql_query, output_lines
+            );
+        }
+
+        Ok(StatementResult {
+            output_lines,
+            has_failed,
+            opt_row_count,
+        })
     }
 
-    fn get_json_value_generators<SerializationType>(
-        column_names: &Vec<String>,
-    ) -> Vec<FieldGenerator>
+    fn get_json_value_generators<SerializationType>(column_names: &[String]) -> Vec<FieldGenerator>
     where
         SerializationType: JsonFieldConversion,
     {
@@ -112,14 +334,14 @@ impl SshConnection {
             .iter()
             .enumerate()
             .map(|(index, column_name)| FieldGenerator {
-                field_name: Self::get_field_name(column_name),
+                field_name: get_field_name(column_name),
                 /* 2026-06-28: Currently the actual column name member is unused but shows the potential difference to
                  * the Rust member name generated by Diesel
                  */
                 column_name: column_name.clone(),
                 column_index: index,
                 to_json: SerializationType::get_json_value_generator(
-                    Self::get_field_name(column_name).as_str(),
+                    get_field_name(column_name).as_str(),
                 ),
             })
             .collect()
@@ -127,11 +349,13 @@ impl SshConnection {
 
     fn convert_to_json(
         field_generators: Vec<FieldGenerator>,
-        rows: &[Vec<String>],
-    ) -> Vec<serde_json::Value> {
+        rows: &[Vec<Option<String>>],
+    ) -> Result<Vec<serde_json::Value>, DatabaseError> {
         if rows.iter().any(|row| row.len() != field_generators.len()) {
-            error!("There are rows of different size than the row of field names");
-            return vec![];
+            let message =
+                String::from("There are rows of different size than the row of field names");
+            error!("{}", message);
+            return Err(DatabaseError::TypeConversion { message });
         }
 
         let mut json_objects: Vec<serde_json::Value> = vec![];
@@ -141,92 +365,356 @@ impl SshConnection {
             for generator in &field_generators {
                 object_properties.insert(
                     generator.field_name.clone(),
-                    (generator.to_json)(row[generator.column_index].as_str()),
+                    (generator.to_json)(row[generator.column_index].as_deref()),
                 );
             }
 
             json_objects.push(serde_json::json!(object_properties));
         }
 
-        json_objects
+        Ok(json_objects)
     }
 
-    // Return row major cells, i.e. a vector of rows, where each row is a vector of coloumns
-    async fn read_cells(&mut self, sql_query: String) -> Option<Vec<Vec<String>>> {
-        let sql_login_result = self
-            .channel
-            .exec(true, self.sql_login_command.as_bytes())
-            .await;
-        match sql_login_result {
-            Ok(_) => {
-                // FIXME Check all write results
-                let _password_write_result = self
-                    .channel
-                    .data(format!("{}\n", self.password).as_bytes())
-                    .await;
-                let _sql_query_write_result = self
-                    .channel
-                    .data(format!("{};\n", sql_query).as_bytes())
-                    .await;
-                let _eof_write_result = self.channel.eof().await;
-
-                let channel_output_lines = self.read_channel_output().await;
-                Some(Self::split_sql_cells(channel_output_lines))
-            }
-            Err(error) => {
-                error!("Could not log in to database due '{}'", error);
-                None
-            }
+    /* NOTE 2026-10-18: The CLIs print error messages to stderr which is discarded. Therefore failed statements can not
+     * be told apart by their cause (e.g. syntax errors and constraint violations).
+     */
+    fn create_statement_error(sql_query: &str) -> DatabaseError {
+        DatabaseError::QuerySyntax {
+            message: format!("Statement '{}' failed", sql_query),
         }
     }
 
-    pub async fn load_member<QueryType>(
+    async fn read_output_lines(&mut self, sql_query: String) -> Result<Vec<String>, DatabaseError> {
+        let statement_result = self.run_statement(sql_query.clone()).await?;
+
+        if statement_result.has_failed {
+            return Err(Self::create_statement_error(&sql_query));
+        }
+
+        Ok(statement_result.output_lines)
+    }
+
+    async fn read_table(
+        &mut self,
+        sql_query: String,
+        column_names: &[&str],
+    ) -> Result<CliTable, DatabaseError> {
+        let wrapped_query = wrap_cli_query(self.get_backend(), &sql_query, column_names);
+        let output_lines = self.read_output_lines(wrapped_query).await?;
+
+        return match parse_cli_table(self.get_backend(), &output_lines) {
+            Some(table) => Ok(table),
+            None => Err(DatabaseError::TypeConversion {
+                message: String::from("Could not interpret output of CLI as table"),
+            }),
+        };
+    }
+
+    // Map the columns of the table to the fields of the objects by their names
+    fn convert_to_objects<ObjectType>(table: &CliTable) -> Result<Vec<ObjectType>, DatabaseError>
+    where
+        ObjectType: JsonFieldConversion + DeserializeOwned,
+    {
+        let field_generators =
+            SshConnection::get_json_value_generators::<ObjectType>(&table.column_names);
+
+        SshConnection::convert_to_json(field_generators, &table.rows)?
+            .into_iter()
+            .map(|value| {
+                serde_json::from_value::<ObjectType>(value).map_err(|error| {
+                    let message = format!("Could not interpret some row as object due '{}'", error);
+                    error!("{}", message);
+                    DatabaseError::TypeConversion { message }
+                })
+            })
+            .collect()
+    }
+
+    pub async fn load<ObjectType, QueryType>(
+        &mut self,
+        sql_query: QueryType,
+    ) -> Result<Vec<ObjectType>, DatabaseError>
+    where
+        ObjectType: JsonFieldConversion + DeserializeOwned,
+        QueryType: QueryStatementWriter,
+        DatabaseBackend: SqlStringifier<QueryType>,
+    {
+        let sql_query_string = self.get_backend().to_sql_string(sql_query);
+        let column_names = ObjectType::get_field_names()
+            .into_iter()
+            .filter_map(ObjectType::get_column_name)
+            .collect::<Vec<&str>>();
+        let table = self.read_table(sql_query_string, &column_names).await?;
+
+        SshConnection::convert_to_objects::<ObjectType>(&table)
+    }
+
+    pub async fn load_integers<QueryType>(
         &mut self,
         sql_query: QueryType,
-    ) -> Option<Vec<models::Member>>
+    ) -> Result<Vec<i32>, DatabaseError>
     where
         QueryType: QueryStatementWriter,
         DatabaseBackend: SqlStringifier<QueryType>,
     {
         let sql_query_string = self.get_backend().to_sql_string(sql_query);
-        self.read_cells(sql_query_string).await.map(|cells| {
-            let cells_split = cells.split_first();
+        let table = self.read_table(sql_query_string, &["value"]).await?;
+
+        // mysql does not print column names of empty results
+        if table.rows.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let opt_column_index = table
+            .column_names
+            .iter()
+            .position(|column_name| column_name == "value");
+
+        if opt_column_index.is_none() {
+            let message = String::from("Query result has no column 'value'");
+            error!("{}", message);
+            return Err(DatabaseError::TypeConversion { message });
+        }
+
+        let column_index = opt_column_index.unwrap();
+        let mut integers = Vec::with_capacity(table.rows.len());
+
+        for row in &table.rows {
+            let opt_integer = row
+                .get(column_index)
+                .and_then(|cell| cell.as_ref())
+                .map(|cell| cell.parse::<i32>());
 
-            if cells_split.is_none() {
-                error!("Could not create objects since column names could not be determined");
-                return None;
+            match opt_integer {
+                Some(Ok(integer)) => integers.push(integer),
+                _ => {
+                    let message = format!("Could not interpret row '{:?}' as integer", row);
+                    error!("{}", message);
+                    return Err(DatabaseError::TypeConversion { message });
+                }
             }
+        }
+
+        Ok(integers)
+    }
 
-            let (column_names, rows) = cells_split.unwrap();
-
-            let field_generators =
-                SshConnection::get_json_value_generators::<models::Member>(column_names);
-
-            let json_result = SshConnection::convert_to_json(field_generators, rows);
-            Some(
-                json_result
-                    .into_iter()
-                    .map(|value| serde_json::from_value::<models::Member>(value))
-                    .filter(|result| match result {
-                        Ok(_member) => true,
-                        Err(error) => {
-                            error!("Could not interpret some row as member due '{}'", error);
-                            false
-                        }
-                    })
-                    .map(Result::unwrap)
-                    .collect::<Vec<models::Member>>(),
-            )
-        })?
-    }
-
-    pub async fn execute_sql<QueryType>(&mut self, sql_query: QueryType) -> Option<usize>
+    pub async fn execute_sql<QueryType>(
+        &mut self,
+        sql_query: QueryType,
+    ) -> Result<usize, DatabaseError>
     where
         DatabaseBackend: SqlStringifier<QueryType>,
     {
         let sql_query_string = self.get_backend().to_sql_string(sql_query);
-        self.read_cells(sql_query_string)
-            .await
-            .map(|cells| cells.len())
+        let statement_result = self.run_statement(sql_query_string.clone()).await?;
+
+        if statement_result.has_failed {
+            return Err(Self::create_statement_error(&sql_query_string));
+        }
+
+        if statement_result.opt_row_count.is_none() {
+            let message = String::from("Could not determine number of affected rows");
+            error!("{}", message);
+            return Err(DatabaseError::UnexpectedResult { message });
+        }
+
+        Ok(statement_result.opt_row_count.unwrap())
+    }
+
+    pub(crate) async fn execute_transaction_control(
+        &mut self,
+        command: &str,
+    ) -> Result<(), DatabaseError> {
+        let statement_result = self.run_statement(String::from(command)).await;
+
+        if statement_result
+            .as_ref()
+            .is_ok_and(|statement_result| !statement_result.has_failed)
+        {
+            return Ok(());
+        }
+
+        error!("Could not execute '{}'", command);
+
+        return match statement_result {
+            Err(error) => Err(error),
+            Ok(_) => Err(Self::create_statement_error(command)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use backend_macros::JsonFieldConversionGenerator;
+    use chrono::NaiveDate;
+    use diesel::QueryableByName;
+    use flutter_rust_bridge::frb;
+    use serde::Deserialize;
+    use speculoos::prelude::*;
+
+    use crate::api::models;
+    use crate::db_connection::LoadableModel;
+
+    use super::*;
+
+    // Model which is not generated from the migrations but loadable the same way
+    #[derive(Debug, PartialEq, QueryableByName, Deserialize, JsonFieldConversionGenerator)]
+    struct Honor {
+        #[diesel(sql_type = diesel::sql_types::Integer)]
+        membership_id: i32,
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        title: String,
+        #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Date>)]
+        awarded_on: Option<NaiveDate>,
+        #[diesel(sql_type = diesel::sql_types::Bool)]
+        is_revoked: bool,
+    }
+
+    fn assert_loadable<ModelType: LoadableModel>() {}
+
+    #[test]
+    fn test_models_are_loadable() {
+        assert_loadable::<models::Member>();
+        assert_loadable::<models::MemberChange>();
+        assert_loadable::<Honor>();
+    }
+
+    #[test]
+    fn test_convert_to_objects() {
+        // Columns are matched by name regardless of their order
+        let table = CliTable {
+            column_names: vec![
+                String::from("title"),
+                String::from("is_revoked"),
+                String::from("membership_id"),
+                String::from("awarded_on"),
+            ],
+            rows: vec![
+                vec![
+                    Some(String::from("Gau-Ehrenzeichen")),
+                    Some(String::from("0")),
+                    Some(String::from("7")),
+                    Some(String::from("2025-05-01")),
+                ],
+                vec![
+                    Some(String::from("Ehrenvorstand")),
+                    Some(String::from("true")),
+                    Some(String::from("12")),
+                    None,
+                ],
+            ],
+        };
+
+        let honors = SshConnection::convert_to_objects::<Honor>(&table);
+        assert_that!(honors).is_equal_to(Ok(vec![
+            Honor {
+                membership_id: 7,
+                title: String::from("Gau-Ehrenzeichen"),
+                awarded_on: NaiveDate::from_ymd_opt(2025, 5, 1),
+                is_revoked: false,
+            },
+            Honor {
+                membership_id: 12,
+                title: String::from("Ehrenvorstand"),
+                awarded_on: None,
+                is_revoked: true,
+            },
+        ]));
+
+        assert_that!(SshConnection::convert_to_objects::<Honor>(
+            &CliTable::default()
+        ))
+        .is_equal_to(Ok(vec![]));
+    }
+
+    #[test]
+    fn test_convert_to_objects_fails_for_invalid_rows() {
+        let mut table = CliTable {
+            column_names: vec![
+                String::from("membership_id"),
+                String::from("title"),
+                String::from("awarded_on"),
+                String::from("is_revoked"),
+            ],
+            rows: vec![vec![
+                Some(String::from("7")),
+                Some(String::from("Gau-Ehrenzeichen")),
+                None,
+                None,
+            ]],
+        };
+
+        // NULL in a field which is not nullable
+        let honors_result = SshConnection::convert_to_objects::<Honor>(&table);
+        assert_that!(honors_result.map_err(|error| error.code()))
+            .is_equal_to(Err(String::from("type_conversion")));
+
+        table.rows[0].pop();
+        let honors_result = SshConnection::convert_to_objects::<Honor>(&table);
+        assert_that!(honors_result.map_err(|error| error.code()))
+            .is_equal_to(Err(String::from("type_conversion")));
+    }
+
+    #[test]
+    fn test_take_line() {
+        let mut output_buffer = "first\r\nsecond\nincompl".as_bytes().to_vec();
+
+        assert_that!(SshConnection::take_line(&mut output_buffer))
+            .is_equal_to(Some(String::from("first")));
+        assert_that!(SshConnection::take_line(&mut output_buffer))
+            .is_equal_to(Some(String::from("second")));
+        assert_that!(SshConnection::take_line(&mut output_buffer)).is_none();
+
+        output_buffer.extend_from_slice("ete\n".as_bytes());
+        assert_that!(SshConnection::take_line(&mut output_buffer))
+            .is_equal_to(Some(String::from("incomplete")));
+        assert_that!(output_buffer.is_empty()).is_true();
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_parse_sentinel_line_pg() {
+        let mut output_lines = vec![String::from("UPDATE 2")];
+
+        let status = SshConnection::parse_sentinel_line(
+            DatabaseBackend::PostgreSql,
+            &mut output_lines,
+            "__green3neo_sentinel_2__ false 2",
+        );
+        assert_that!(status).is_equal_to((false, Some(2)));
+        assert_that!(output_lines.len()).is_equal_to(1);
+
+        let status = SshConnection::parse_sentinel_line(
+            DatabaseBackend::PostgreSql,
+            &mut output_lines,
+            "__green3neo_sentinel_3__ true 0",
+        );
+        assert_that!(status).is_equal_to((true, Some(0)));
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_parse_sentinel_line_mysql() {
+        let mut output_lines = vec![
+            String::from("value"),
+            String::from("42"),
+            String::from(MYSQL_SENTINEL_HEADER),
+        ];
+
+        // Queries report -1 affected rows
+        let status = SshConnection::parse_sentinel_line(
+            DatabaseBackend::MySql,
+            &mut output_lines,
+            "__green3neo_sentinel_2__\t0\t-1",
+        );
+        assert_that!(status).is_equal_to((false, None));
+        assert_that!(output_lines).is_equal_to(vec![String::from("value"), String::from("42")]);
+
+        let status = SshConnection::parse_sentinel_line(
+            DatabaseBackend::MySql,
+            &mut vec![],
+            "__green3neo_sentinel_3__\t1\t-1",
+        );
+        assert_that!(status).is_equal_to((true, None));
     }
 }
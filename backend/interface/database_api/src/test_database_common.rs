@@ -1,22 +1,39 @@
 use std::ops::DerefMut;
 
 use backend_testing::testing;
+use database_types::column_value::ColumnValue;
+use database_types::connection_description::ConnectionDescription;
+#[cfg(any(feature = "mysql", feature = "postgres"))]
 use database_types::connection_description::{
-    ConnectionDescription, DatabaseBackend, SshTunnelDescription,
+    DatabaseBackend, SshAuthentication, SshTunnelDescription, SshTunnelMode, TimeoutOptions,
+    TlsOptions,
 };
 use sea_query::{ColumnDef, Table};
 #[cfg(feature = "mysql")]
 use sqlx::MySql;
 #[cfg(feature = "postgres")]
 use sqlx::Postgres;
+#[cfg(feature = "sqlite")]
+use sqlx::Sqlite;
 use sqlx::{Database, Pool, any::install_default_drivers, pool::PoolConnection};
 
-use crate::{connection::get_connection, db_connection::DbConnection};
+use crate::{
+    api::host_key::{
+        HostKeyStatus, accept_host_key_impl, get_host_key_status_impl, set_known_hosts_path,
+    },
+    api::member::MemberValue,
+    api::schema_version::upgrade_schema_impl,
+    connection::get_connection,
+    db_connection::DbConnection,
+};
 
 pub trait GetCurrentDBName {
     async fn get_current_db_name(connection: &mut PoolConnection<Self>) -> String
     where
         Self: Database;
+
+    // Settings of the test database apart from its name
+    fn get_connection_description() -> ConnectionDescription;
 }
 
 #[cfg(feature = "postgres")]
@@ -27,6 +44,10 @@ impl GetCurrentDBName for Postgres {
             .await
             .expect("Querying current database name failed")
     }
+
+    fn get_connection_description() -> ConnectionDescription {
+        read_connection_from_environment()
+    }
 }
 
 #[cfg(feature = "mysql")]
@@ -37,8 +58,28 @@ impl GetCurrentDBName for MySql {
             .await
             .expect("Querying current database name failed")
     }
+
+    fn get_connection_description() -> ConnectionDescription {
+        read_connection_from_environment()
+    }
+}
+
+// The database is a file created by sqlx hence no environment variables are required
+#[cfg(feature = "sqlite")]
+impl GetCurrentDBName for Sqlite {
+    async fn get_current_db_name(connection: &mut PoolConnection<Self>) -> String {
+        sqlx::query_scalar::<_, String>("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_one(connection.deref_mut())
+            .await
+            .expect("Querying current database name failed")
+    }
+
+    fn get_connection_description() -> ConnectionDescription {
+        ConnectionDescription::for_sqlite_file(String::new())
+    }
 }
 
+#[cfg(any(feature = "mysql", feature = "postgres"))]
 fn read_connection_from_environment() -> ConnectionDescription {
     let db_protocol = std::env::var("BUILD_DB_PROTOCOL").unwrap();
     let db_host = std::env::var("BUILD_DB_HOST").unwrap();
@@ -51,6 +92,12 @@ fn read_connection_from_environment() -> ConnectionDescription {
     let ssh_port = std::env::var("SSH_PORT");
     let ssh_user = std::env::var("SSH_USER");
     let ssh_password = std::env::var("SSH_PASSWORD");
+    // Optional since port forwarding is used by default
+    let ssh_tunnel_mode = match std::env::var("SSH_TUNNEL_MODE").as_deref() {
+        Ok("remote_cli") => SshTunnelMode::RemoteCli,
+        Ok("port_forwarding") | Err(_) => SshTunnelMode::PortForwarding,
+        Ok(mode) => panic!("Unknown SSH tunnel mode '{}'", mode),
+    };
 
     let ssh_tunnel = if ssh_host.is_ok()
         && ssh_port.is_ok()
@@ -60,8 +107,10 @@ fn read_connection_from_environment() -> ConnectionDescription {
         Some(SshTunnelDescription {
             host: ssh_host.unwrap(),
             port: ssh_port.unwrap().parse().unwrap(),
-            password: ssh_password.unwrap(),
+            authentication: SshAuthentication::Password(ssh_password.unwrap()),
             username: ssh_user.unwrap(),
+            jump_hosts: vec![],
+            mode: ssh_tunnel_mode,
         })
     } else if ssh_host.is_err() && ssh_port.is_err() && ssh_user.is_err() && ssh_password.is_err() {
         None
@@ -82,11 +131,16 @@ fn read_connection_from_environment() -> ConnectionDescription {
         user: db_user,
         password: db_password,
         name: db_name,
-        ssh_tunnel: ssh_tunnel,
+        ssh_tunnel,
+        tls: TlsOptions::default(),
+        socket_path: None,
+        cli_command_template: std::env::var("DB_CLI_COMMAND_TEMPLATE").ok(),
+        timeouts: TimeoutOptions::default(),
     }
 }
 
-pub async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+// Description of the test database whose SSH host keys are trusted
+pub async fn setup_test_description<DB>(sqlx_pool: Pool<DB>) -> ConnectionDescription
 where
     DB: Database + GetCurrentDBName,
 {
@@ -96,9 +150,32 @@ where
 
     let mut sqlx_connection = sqlx_pool.acquire().await.unwrap();
 
-    let mut connection_description = read_connection_from_environment();
+    let mut connection_description = DB::get_connection_description();
     connection_description.name = DB::get_current_db_name(&mut sqlx_connection).await;
 
+    // The SSH server of the test environment is trusted without touching the known hosts of the user
+    set_known_hosts_path(
+        std::env::temp_dir().join(format!("known_hosts_database_api_{}", std::process::id())),
+    );
+
+    if connection_description.ssh_tunnel.is_some() {
+        let ssh_tunnel = connection_description.ssh_tunnel.as_ref().unwrap();
+
+        while let HostKeyStatus::Unknown { fingerprint } =
+            get_host_key_status_impl(ssh_tunnel).await
+        {
+            assert!(accept_host_key_impl(ssh_tunnel, &fingerprint).await);
+        }
+    }
+
+    connection_description
+}
+
+pub async fn setup_test<DB>(sqlx_pool: Pool<DB>) -> DbConnection
+where
+    DB: Database + GetCurrentDBName,
+{
+    let connection_description = setup_test_description(sqlx_pool).await;
     let mut connection = get_connection(connection_description).await.unwrap();
 
     let table_creation_statement = Table::create()
@@ -124,6 +201,36 @@ where
     connection
 }
 
+pub async fn create_member_tables(connection: &mut DbConnection) {
+    let schema_version_result = upgrade_schema_impl(connection).await;
+    assert!(schema_version_result.is_ok(), "Applying migrations failed");
+}
+
+// Values of all member columns which have no default
+pub fn create_member_values(surname: &str) -> Vec<MemberValue> {
+    vec![
+        ("prename", ColumnValue::Text(String::from("Jane"))),
+        ("surname", ColumnValue::Text(surname.to_owned())),
+        ("is_male", ColumnValue::Boolean(false)),
+        ("street", ColumnValue::Text(String::from("Main Street"))),
+        ("house_number", ColumnValue::Text(String::from("1"))),
+        ("zip_code", ColumnValue::Text(String::from("12345"))),
+        ("city", ColumnValue::Text(String::from("Springfield"))),
+        ("is_active", ColumnValue::Boolean(true)),
+        (
+            "iban",
+            ColumnValue::Text(String::from("DE02120300000000202051")),
+        ),
+        ("bic", ColumnValue::Text(String::from("BYLADEM1001"))),
+    ]
+    .into_iter()
+    .map(|(column, value)| MemberValue {
+        column: column.to_owned(),
+        value,
+    })
+    .collect()
+}
+
 pub fn tear_down(expected_num_severe_messages: usize) {
     testing::tear_down(expected_num_severe_messages);
 }
@@ -0,0 +1,149 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use database_types::column_value::ColumnValue;
+use sea_query::Expr;
+
+use crate::json_field_conversion::{FieldType, FieldTypeInfo};
+
+pub fn to_expr(value: &ColumnValue) -> Expr {
+    match value {
+        ColumnValue::Text(text) => Expr::value(text.clone()),
+        ColumnValue::Integer(integer) => Expr::value(*integer),
+        ColumnValue::Boolean(boolean) => Expr::value(*boolean),
+        ColumnValue::Double(double) => Expr::value(*double),
+        ColumnValue::Date(date) => Expr::value(*date),
+        ColumnValue::DateTime(date_time) => Expr::value(*date_time),
+        ColumnValue::Null => Expr::null(),
+    }
+}
+
+pub fn matches_field_type(value: &ColumnValue, type_info: &FieldTypeInfo) -> bool {
+    match value {
+        ColumnValue::Text(_) => type_info.field_type == FieldType::Text,
+        ColumnValue::Integer(_) => type_info.field_type == FieldType::Integer,
+        ColumnValue::Boolean(_) => type_info.field_type == FieldType::Boolean,
+        ColumnValue::Double(_) => type_info.field_type == FieldType::Double,
+        ColumnValue::Date(_) => type_info.field_type == FieldType::Date,
+        ColumnValue::DateTime(_) => type_info.field_type == FieldType::DateTime,
+        ColumnValue::Null => type_info.nullable,
+    }
+}
+
+pub fn from_json_value(
+    value: &serde_json::Value,
+    type_info: &FieldTypeInfo,
+) -> Option<ColumnValue> {
+    match (value, &type_info.field_type) {
+        (serde_json::Value::Null, _) => Some(ColumnValue::Null),
+        (serde_json::Value::String(text), FieldType::Text) => Some(ColumnValue::Text(text.clone())),
+        (serde_json::Value::String(text), FieldType::Date) => {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .map(ColumnValue::Date)
+        }
+        (serde_json::Value::String(text), FieldType::DateTime) => {
+            parse_date_time(text).map(ColumnValue::DateTime)
+        }
+        (serde_json::Value::Number(number), FieldType::Integer) => {
+            number.as_i64().map(ColumnValue::Integer)
+        }
+        (serde_json::Value::Number(number), FieldType::Double) => {
+            number.as_f64().map(ColumnValue::Double)
+        }
+        (serde_json::Value::Bool(boolean), FieldType::Boolean) => {
+            Some(ColumnValue::Boolean(*boolean))
+        }
+        _ => None,
+    }
+}
+
+/* Timestamps of PostgreSQL are separated by "T" whereas MySQL and SQLite use a space. Offsets e.g. of timestamptz are
+ * applied such that the result is in UTC.
+ */
+fn parse_date_time(text: &str) -> Option<NaiveDateTime> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(text) {
+        return Some(date_time.naive_utc());
+    }
+
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| {
+            DateTime::parse_from_str(text, &format!("{}%#z", format))
+                .map(|date_time| date_time.naive_utc())
+                .or_else(|_| NaiveDateTime::parse_from_str(text, format))
+                .ok()
+        })
+}
+
+// Values of JSON objects created by the databases differ from the ones of the frontend e.g. booleans are numbers
+pub fn from_database_json_value(
+    value: &serde_json::Value,
+    type_info: &FieldTypeInfo,
+) -> Option<ColumnValue> {
+    match (value, &type_info.field_type) {
+        (serde_json::Value::Number(number), FieldType::Boolean) => number
+            .as_i64()
+            .map(|integer| ColumnValue::Boolean(integer != 0)),
+        // Dates may be stored including a time e.g. in SQLite
+        (serde_json::Value::String(text), FieldType::Date) => text
+            .get(..10)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(ColumnValue::Date),
+        (serde_json::Value::String(text), FieldType::DateTime) => {
+            parse_date_time(text).map(ColumnValue::DateTime)
+        }
+        // Columns of unknown types are read as text
+        (serde_json::Value::Number(_) | serde_json::Value::Bool(_), FieldType::Text) => {
+            Some(ColumnValue::Text(value.to_string()))
+        }
+        _ => from_json_value(value, type_info),
+    }
+}
+
+// Counterpart of from_json_value
+pub fn to_json_value(value: &ColumnValue) -> serde_json::Value {
+    match value {
+        ColumnValue::Text(text) => serde_json::Value::String(text.clone()),
+        ColumnValue::Integer(integer) => serde_json::Value::from(*integer),
+        ColumnValue::Boolean(boolean) => serde_json::Value::Bool(*boolean),
+        ColumnValue::Double(double) => serde_json::Value::from(*double),
+        ColumnValue::Date(date) => serde_json::Value::String(date.format("%Y-%m-%d").to_string()),
+        ColumnValue::DateTime(date_time) => {
+            serde_json::Value::String(date_time.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+        }
+        ColumnValue::Null => serde_json::Value::Null,
+    }
+}
+
+// Textual representation e.g. for storing values of arbitrary columns in a single column
+pub fn to_text(value: &ColumnValue) -> Option<String> {
+    match value {
+        ColumnValue::Text(text) => Some(text.clone()),
+        ColumnValue::Integer(integer) => Some(integer.to_string()),
+        ColumnValue::Boolean(boolean) => Some(boolean.to_string()),
+        ColumnValue::Double(double) => Some(double.to_string()),
+        ColumnValue::Date(date) => Some(date.format("%Y-%m-%d").to_string()),
+        ColumnValue::DateTime(date_time) => {
+            Some(date_time.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+        }
+        ColumnValue::Null => None,
+    }
+}
+
+pub fn from_text(opt_text: Option<&str>, type_info: &FieldTypeInfo) -> Option<ColumnValue> {
+    if opt_text.is_none() {
+        return Some(ColumnValue::Null);
+    }
+
+    let text = opt_text.unwrap();
+
+    match type_info.field_type {
+        FieldType::Text => Some(ColumnValue::Text(text.to_owned())),
+        FieldType::Integer => text.parse::<i64>().ok().map(ColumnValue::Integer),
+        FieldType::Boolean => text.parse::<bool>().ok().map(ColumnValue::Boolean),
+        FieldType::Double => text.parse::<f64>().ok().map(ColumnValue::Double),
+        FieldType::Date => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .map(ColumnValue::Date),
+        FieldType::DateTime => parse_date_time(text).map(ColumnValue::DateTime),
+    }
+}
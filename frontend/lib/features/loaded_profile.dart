@@ -8,6 +8,7 @@ import 'package:green3neo/features/feature.dart';
 import 'package:green3neo/interface/backend_api/api.dart' as backend_api;
 import 'package:green3neo/interface/backend_api/api/profile.dart';
 import 'package:green3neo/interface/database_api/api.dart';
+import 'package:green3neo/interface/database_api/api/connection_manager.dart';
 import 'package:green3neo/interface/sepa_api/api.dart';
 
 part 'loaded_profile.freezed.dart';
@@ -24,6 +25,51 @@ class LoadedProfile with _$LoadedProfile {
     @Default(null) this.connection,
   });
 
+  static backend_api.SshAuthentication _toMirroredAuthentication(
+      SshAuthentication authentication) {
+    return switch (authentication) {
+      SshAuthentication_Password(field0: final String password) =>
+        backend_api.SshAuthentication.password(password),
+      SshAuthentication_PrivateKey(
+        path: final String path,
+        passphrase: final String? passphrase
+      ) =>
+        backend_api.SshAuthentication.privateKey(
+            path: path, passphrase: passphrase),
+      SshAuthentication_Agent() => const backend_api.SshAuthentication.agent(),
+    };
+  }
+
+  static SshAuthentication _fromMirroredAuthentication(
+      backend_api.SshAuthentication authentication) {
+    return switch (authentication) {
+      backend_api.SshAuthentication_Password(field0: final String password) =>
+        SshAuthentication.password(password),
+      backend_api.SshAuthentication_PrivateKey(
+        path: final String path,
+        passphrase: final String? passphrase
+      ) =>
+        SshAuthentication.privateKey(path: path, passphrase: passphrase),
+      backend_api.SshAuthentication_Agent() => const SshAuthentication.agent(),
+    };
+  }
+
+  static backend_api.SshHop _toMirroredHop(SshHop hop) {
+    return backend_api.SshHop(
+        username: hop.username,
+        authentication: _toMirroredAuthentication(hop.authentication),
+        host: hop.host,
+        port: hop.port);
+  }
+
+  static SshHop _fromMirroredHop(backend_api.SshHop hop) {
+    return SshHop(
+        username: hop.username,
+        authentication: _fromMirroredAuthentication(hop.authentication),
+        host: hop.host,
+        port: hop.port);
+  }
+
   Future<void> save() async {
     final backend_api.Creditor? mirroredCreditor = (creditor == null)
         ? null
@@ -43,6 +89,34 @@ class LoadedProfile with _$LoadedProfile {
                 user: connection!.user,
                 password: connection!.password,
                 name: connection!.name,
+                sshTunnel: (connection!.sshTunnel == null)
+                    ? null
+                    : backend_api.SshTunnelDescription(
+                        username: connection!.sshTunnel!.username,
+                        authentication: _toMirroredAuthentication(
+                            connection!.sshTunnel!.authentication),
+                        host: connection!.sshTunnel!.host,
+                        port: connection!.sshTunnel!.port,
+                        jumpHosts: connection!.sshTunnel!.jumpHosts
+                            .map(_toMirroredHop)
+                            .toList(),
+                        mode: backend_api.SshTunnelMode
+                            .values[connection!.sshTunnel!.mode.index],
+                      ),
+                tls: backend_api.TlsOptions(
+                  mode: backend_api.TlsMode.values[connection!.tls.mode.index],
+                  caCertificatePath: connection!.tls.caCertificatePath,
+                  clientCertificatePath: connection!.tls.clientCertificatePath,
+                  clientKeyPath: connection!.tls.clientKeyPath,
+                ),
+                socketPath: connection!.socketPath,
+                cliCommandTemplate: connection!.cliCommandTemplate,
+                timeouts: backend_api.TimeoutOptions(
+                  connectSeconds: connection!.timeouts.connectSeconds,
+                  authenticationSeconds:
+                      connection!.timeouts.authenticationSeconds,
+                  querySeconds: connection!.timeouts.querySeconds,
+                ),
               );
 
     await saveProfile(
@@ -52,6 +126,9 @@ class LoadedProfile with _$LoadedProfile {
       ),
     );
 
+    // Connections of the previous profile are not used anymore
+    await closeAllConnections();
+
     final getIt = GetIt.instance;
     getIt.resetLazySingleton<LoadedProfile>();
   }
@@ -87,9 +164,14 @@ class LoadedProfileFeature implements Feature {
             ? null
             : SshTunnelDescription(
                 username: mirroredSshTunnel.username,
-                password: mirroredSshTunnel.password,
+                authentication: LoadedProfile._fromMirroredAuthentication(
+                    mirroredSshTunnel.authentication),
                 host: mirroredSshTunnel.host,
-                port: mirroredSshTunnel.port);
+                port: mirroredSshTunnel.port,
+                jumpHosts: mirroredSshTunnel.jumpHosts
+                    .map(LoadedProfile._fromMirroredHop)
+                    .toList(),
+                mode: SshTunnelMode.values[mirroredSshTunnel.mode.index]);
 
         final ConnectionDescription? connection = (mirroredConnection == null)
             ? null
@@ -101,7 +183,20 @@ class LoadedProfileFeature implements Feature {
                 user: mirroredConnection.user,
                 password: mirroredConnection.password,
                 name: mirroredConnection.name,
-                sshTunnel: sshTunnel);
+                sshTunnel: sshTunnel,
+                tls: TlsOptions(
+                    mode: TlsMode.values[mirroredConnection.tls.mode.index],
+                    caCertificatePath: mirroredConnection.tls.caCertificatePath,
+                    clientCertificatePath:
+                        mirroredConnection.tls.clientCertificatePath,
+                    clientKeyPath: mirroredConnection.tls.clientKeyPath),
+                socketPath: mirroredConnection.socketPath,
+                cliCommandTemplate: mirroredConnection.cliCommandTemplate,
+                timeouts: TimeoutOptions(
+                    connectSeconds: mirroredConnection.timeouts.connectSeconds,
+                    authenticationSeconds:
+                        mirroredConnection.timeouts.authenticationSeconds,
+                    querySeconds: mirroredConnection.timeouts.querySeconds));
 
         return LoadedProfile._create(
           creditor: creditor,
@@ -1,5 +1,7 @@
 import 'package:get_it/get_it.dart';
 import 'package:green3neo/features/loaded_profile.dart';
+import 'package:green3neo/interface/database_api/api.dart';
+import 'package:green3neo/interface/database_api/api/database_error.dart';
 import 'package:green3neo/interface/database_api/api/member.dart';
 import 'package:green3neo/localizer.dart';
 import 'package:logging/logging.dart';
@@ -20,17 +22,57 @@ Future<bool> commitDataChanges(List<ChangeRecord> changeRecords) async {
     return false;
   }
 
-  final BigInt actualNumUpdatedRows =
-      await changeMember(connection: profile.connection!, changes: records);
+  final ChangeMemberResult result;
+  try {
+    result =
+        await changeMember(connection: profile.connection!, changes: records);
+  } on DatabaseError catch (error) {
+    _logger.severe(
+        "Changing members failed due '${error.message}' (${error.code()})");
+    return false;
+  }
 
-  // FIXME Check which rows could not be updated
-  final expectedNumUpdatedRows = BigInt.from(records.length);
-  if (actualNumUpdatedRows != expectedNumUpdatedRows) {
+  final RollbackCause? rollbackCause = result.rollbackCause;
+  if (rollbackCause != null) {
+    final DatabaseError? error = rollbackCause.error;
     _logger.warning(
-        "Updated $actualNumUpdatedRows but $expectedNumUpdatedRows were exepcted");
+        "Rolled back all changes due to member ${rollbackCause.membershipid} "
+        "(statement: '${rollbackCause.statement}'"
+        "${error == null ? "" : ", error: ${error.code()}"})");
   }
 
-  return true;
+  final failedResults = result.recordResults
+      .where((recordResult) => recordResult.status is! ChangeStatus_Applied);
+  for (final failedResult in failedResults) {
+    switch (failedResult.status) {
+      case ChangeStatus_Conflict(field0: final conflict):
+        _logger.warning(
+            "Column '${conflict.column}' of member ${conflict.membershipid} "
+            "was expected to be '${columnValueToString(conflict.expectedValue)}' "
+            "but is '${columnValueToString(conflict.actualValue)}'");
+        break;
+      default:
+        _logger.warning(
+            "Changing column '${failedResult.record.column}' of member "
+            "${failedResult.record.membershipid} failed "
+            "(${failedResult.status})");
+        break;
+    }
+  }
+
+  return failedResults.isEmpty;
+}
+
+String columnValueToString(ColumnValue value) {
+  return switch (value) {
+    ColumnValue_Text(field0: final text) => text,
+    ColumnValue_Integer(field0: final integer) => integer.toString(),
+    ColumnValue_Boolean(field0: final boolean) => boolean.toString(),
+    ColumnValue_Double(field0: final double) => double.toString(),
+    ColumnValue_Date(field0: final date) => date.toIso8601String(),
+    ColumnValue_DateTime(field0: final dateTime) => dateTime.toIso8601String(),
+    ColumnValue_Null() => "null",
+  };
 }
 
 Widget visualizeChanges(
@@ -51,8 +93,8 @@ Widget visualizeChanges(
           children: [
             Text(record.membershipid.toString()),
             Text(record.column),
-            Text(record.previousValue ?? "null"),
-            Text(record.newValue ?? "null"),
+            Text(columnValueToString(record.previousValue)),
+            Text(columnValueToString(record.newValue)),
           ],
         ),
     ],
@@ -80,9 +122,11 @@ List<ChangeRecord> mergeChangeRecords(List<ChangeRecord> changeRecords) {
           throw Exception(
               "Record changes (membershipid: ${record.membershipid}, "
               "column: ${record.column}) do not match. Previous changed "
-              "from '${existingMergeRecord.previousValue}' to "
-              "'${existingMergeRecord.newValue}', next is expected to change "
-              "from '${record.previousValue}' to '${record.newValue}'");
+              "from '${columnValueToString(existingMergeRecord.previousValue)}' "
+              "to '${columnValueToString(existingMergeRecord.newValue)}', next "
+              "is expected to change from "
+              "'${columnValueToString(record.previousValue)}' to "
+              "'${columnValueToString(record.newValue)}'");
         }
 
         mergedChangeRecords[existingMergeRecordIndex] = ChangeRecord(
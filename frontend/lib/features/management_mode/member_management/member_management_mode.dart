@@ -78,6 +78,7 @@ class MemberManagementPage extends StatelessWidget {
     final MemberView memberView = getIt<MemberViewFeature>().widget;
     memberView.viewMode = ViewMode.editable;
     memberView.propertyFilter = null;
+    memberView.memberQuery = null;
 
     return Column(
       children: [
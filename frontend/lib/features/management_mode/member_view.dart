@@ -3,7 +3,11 @@ import 'package:flutter/foundation.dart';
 import 'package:green3neo/components/table_view.dart';
 import 'package:green3neo/features/loaded_profile.dart';
 import 'package:green3neo/features/widget_feature.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge_for_generated.dart';
+import 'package:green3neo/interface/database_api/api.dart';
+import 'package:green3neo/interface/database_api/api/database_error.dart';
 import 'package:green3neo/interface/database_api/api/member.dart';
+import 'package:green3neo/interface/database_api/api/member_query.dart';
 import 'package:green3neo/interface/database_api/api/models.dart';
 import 'package:green3neo/localizer.dart';
 import 'package:listen_it/listen_it.dart';
@@ -32,6 +36,7 @@ class MemberView extends WatchingWidget {
   final _tableViewSource = TableViewSource<Member>();
   final _viewMode = ValueNotifier<ViewMode>(ViewMode.readOnly);
   final _propertyFilter = ValueNotifier<bool Function(String)?>(null);
+  final _memberQuery = ValueNotifier<MemberQuery?>(null);
 
   final _changeRecords = ListNotifier<ChangeRecord>(data: []);
   final _selectedRecords = ListNotifier<Member>(data: []);
@@ -47,19 +52,24 @@ class MemberView extends WatchingWidget {
         return false;
       }
 
-      return getAllMembers(connection: profile.connection!).then((members) {
+      final MemberQuery? memberQuery = _memberQuery.value;
+      final Future<List<Member>> loadedMembers = memberQuery == null
+          ? getAllMembers(connection: profile.connection!)
+          : getMembers(connection: profile.connection!, query: memberQuery);
+
+      return loadedMembers.then((members) {
         _tableViewSource.content.clear();
         _changeRecords.clear();
-
-        if (members == null) {
-          _logger.severe("Loading members returned null result");
-          return false;
-        }
-
         _tableViewSource.content.addAll(
           members.map((m) => TableViewSourceEntry(value: m, selected: false)),
         );
         return true;
+      }).onError<DatabaseError>((error, stackTrace) {
+        _tableViewSource.content.clear();
+        _changeRecords.clear();
+        _logger.severe(
+            "Loading members failed due '${error.message}' (${error.code()})");
+        return false;
       });
     });
   }
@@ -70,28 +80,45 @@ class MemberView extends WatchingWidget {
     _propertyFilter.value = propertyFilter;
   }
 
+  // Filters, sort orders and pagination applied by the database
+  set memberQuery(MemberQuery? memberQuery) => _memberQuery.value = memberQuery;
+
   ListNotifier<ChangeRecord> get changeRecords => _changeRecords;
 
   ListNotifier<Member> get selectedRecords => _selectedRecords;
 
+  static ColumnValue _toColumnValue(SupportedType? cellValue) {
+    return switch (cellValue) {
+      null => const ColumnValue.null_(),
+      IntVariant(value: final int value) =>
+        ColumnValue.integer(PlatformInt64Util.from(value)),
+      StringVariant(value: final String value) => ColumnValue.text(value),
+      BoolVariant(value: final bool value) => ColumnValue.boolean(value),
+      UnsupportedVariant(value: final dynamic value) =>
+        ColumnValue.text(value.toString()),
+    };
+  }
+
+  // Dart properties are camelCase whereas database columns are snake_case
+  static String _toColumnName(String propertyName) {
+    return propertyName.replaceAllMapped(
+      RegExp(r"[A-Z]"),
+      (match) => "_${match.group(0)!.toLowerCase()}",
+    );
+  }
+
   void _onCellChanged(
     Member member,
     String setterName,
     SupportedType? previousCellValue,
     SupportedType? newCellValue,
   ) {
-    var internalPreviousValue = previousCellValue?.value;
-    var internalNewValue = newCellValue?.value;
     _changeRecords.add(
       ChangeRecord(
         membershipid: member.membershipId,
-        column: setterName,
-        previousValue: (internalPreviousValue != null)
-            ? internalPreviousValue.toString()
-            : null,
-        newValue: (internalNewValue != null)
-            ? internalNewValue.toString()
-            : null,
+        column: _toColumnName(setterName),
+        previousValue: _toColumnValue(previousCellValue),
+        newValue: _toColumnValue(newCellValue),
       ),
     );
   }
@@ -126,6 +153,7 @@ class MemberView extends WatchingWidget {
   Widget build(BuildContext context) {
     _viewMode.addListener(() => _reinitTableSource(context));
     _propertyFilter.addListener(() => _reinitTableSource(context));
+    _memberQuery.addListener(() => forceReloadDataFromDB());
     _reinitTableSource(context);
 
     // FIXME Visualize failed reload
@@ -45,6 +45,7 @@ class SepaManagementPage extends StatelessWidget {
 
     final MemberView memberView = getIt<MemberViewFeature>().widget;
     memberView.viewMode = ViewMode.selectable;
+    memberView.memberQuery = null;
     memberView.propertyFilter = (String propertyName) {
       return [
         "membershipid",
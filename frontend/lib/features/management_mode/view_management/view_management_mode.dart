@@ -1,5 +1,6 @@
 import 'package:green3neo/features/management_mode/management_mode.dart';
 import 'package:green3neo/features/management_mode/member_view.dart';
+import 'package:green3neo/interface/database_api/api/member_query.dart';
 import 'package:green3neo/localizer.dart';
 import 'package:listen_it/listen_it.dart';
 import 'package:material_ui/material_ui.dart';
@@ -7,6 +8,7 @@ import 'package:watch_it/watch_it.dart';
 
 class ViewManagementPage extends WatchingWidget {
   final _lastMemberSourceUpdate = ValueNotifier<DateTime?>(null);
+  final _searchText = ValueNotifier<String>("");
 
   ViewManagementPage._create({super.key});
 
@@ -18,6 +20,21 @@ class ViewManagementPage extends WatchingWidget {
     });
   }
 
+  // Members whose prename or surname contains the search text
+  static MemberQuery? _createSearchQuery(String searchText) {
+    if (searchText.isEmpty) {
+      return null;
+    }
+
+    return MemberQuery(
+      filter: MemberFilter.or([
+        MemberFilter.contains(column: "prename", text: searchText),
+        MemberFilter.contains(column: "surname", text: searchText),
+      ]),
+      sortOrders: [SortOrder(column: "surname", ascending: true)],
+    );
+  }
+
   static String _formatLastDate(DateTime? date, BuildContext context) {
     if (date == null) {
       return Localizer.instance.text((l) => l.noDate);
@@ -35,6 +52,7 @@ class ViewManagementPage extends WatchingWidget {
     final MemberView memberView = getIt<MemberViewFeature>().widget;
     memberView.viewMode = ViewMode.readOnly;
     memberView.propertyFilter = null;
+    memberView.memberQuery = _createSearchQuery(_searchText.value);
 
     final formattedLastDate = watch(_lastMemberSourceUpdate)
         .map((value) => _formatLastDate(value, context));
@@ -48,6 +66,17 @@ class ViewManagementPage extends WatchingWidget {
               child: Text(Localizer.instance.text((l) => l.updateData)),
             ),
             Text(formattedLastDate.value),
+            Expanded(
+              child: TextField(
+                decoration: InputDecoration(
+                  labelText: Localizer.instance.text((l) => l.searchMembers),
+                ),
+                onChanged: (searchText) {
+                  _searchText.value = searchText;
+                  memberView.memberQuery = _createSearchQuery(searchText);
+                },
+              ),
+            ),
           ],
         ),
         Expanded(child: memberView),
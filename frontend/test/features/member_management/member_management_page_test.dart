@@ -1,5 +1,6 @@
 import 'package:flutter_test/flutter_test.dart';
 import 'package:green3neo/features/management_mode/member_management/change_record_utility.dart';
+import 'package:green3neo/interface/database_api/api.dart';
 import 'package:green3neo/interface/database_api/api/member.dart';
 
 class MergeChangeRecordsTestCase {
@@ -17,68 +18,68 @@ Map<String, MergeChangeRecordsTestCase> goodMergeTestCases = {
     ChangeRecord(
         column: "surname",
         membershipid: 1,
-        previousValue: "Ritson",
-        newValue: "Smith")
+        previousValue: ColumnValue.text("Ritson"),
+        newValue: ColumnValue.text("Smith"))
   ], mergedRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 1,
-        previousValue: "Ritson",
-        newValue: "Smith"),
+        previousValue: ColumnValue.text("Ritson"),
+        newValue: ColumnValue.text("Smith")),
   ]),
   "multiple": MergeChangeRecordsTestCase(initialRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 1,
-        previousValue: "Ritson",
-        newValue: "Smith"),
+        previousValue: ColumnValue.text("Ritson"),
+        newValue: ColumnValue.text("Smith")),
     ChangeRecord(
         column: "surname",
         membershipid: 2,
-        previousValue: "Clears",
-        newValue: "Johnson"),
+        previousValue: ColumnValue.text("Clears"),
+        newValue: ColumnValue.text("Johnson")),
   ], mergedRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 1,
-        previousValue: "Ritson",
-        newValue: "Smith"),
+        previousValue: ColumnValue.text("Ritson"),
+        newValue: ColumnValue.text("Smith")),
     ChangeRecord(
         column: "surname",
         membershipid: 2,
-        previousValue: "Clears",
-        newValue: "Johnson"),
+        previousValue: ColumnValue.text("Clears"),
+        newValue: ColumnValue.text("Johnson")),
   ]),
   "transitive": MergeChangeRecordsTestCase(initialRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 3,
-        previousValue: "Bliven",
-        newValue: "Green"),
+        previousValue: ColumnValue.text("Bliven"),
+        newValue: ColumnValue.text("Green")),
     ChangeRecord(
         column: "surname",
         membershipid: 3,
-        previousValue: "Green",
-        newValue: "Red"),
+        previousValue: ColumnValue.text("Green"),
+        newValue: ColumnValue.text("Red")),
   ], mergedRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 3,
-        previousValue: "Bliven",
-        newValue: "Red"),
+        previousValue: ColumnValue.text("Bliven"),
+        newValue: ColumnValue.text("Red")),
   ]),
   "mismatch original": MergeChangeRecordsTestCase(initialRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 101,
-        previousValue: "NotAdds",
-        newValue: "AnotherSurname"),
+        previousValue: ColumnValue.text("NotAdds"),
+        newValue: ColumnValue.text("AnotherSurname")),
   ], mergedRecords: [
     ChangeRecord(
         column: "surname",
         membershipid: 101,
-        previousValue: "NotAdds",
-        newValue: "AnotherSurname"),
+        previousValue: ColumnValue.text("NotAdds"),
+        newValue: ColumnValue.text("AnotherSurname")),
   ]),
   // FIXME What about changing primary key values?
 };
@@ -88,13 +89,13 @@ Map<String, MergeChangeRecordsTestCase> badMergeTestCases = {
     ChangeRecord(
         column: "surname",
         membershipid: 100,
-        previousValue: "Hargitt",
-        newValue: "Green"),
+        previousValue: ColumnValue.text("Hargitt"),
+        newValue: ColumnValue.text("Green")),
     ChangeRecord(
         column: "surname",
         membershipid: 100,
-        previousValue: "NotGreen",
-        newValue: "Red"),
+        previousValue: ColumnValue.text("NotGreen"),
+        newValue: ColumnValue.text("Red")),
   ], mergedRecords: []),
 };
 
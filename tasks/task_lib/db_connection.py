@@ -1,5 +1,6 @@
 import psycopg2
 import re
+import sqlite3
 from psycopg2._psycopg import connection as PgConnection
 from os import getenv
 from typing import Dict, List, Tuple, Any, Optional, Union
@@ -7,7 +8,9 @@ from pathlib import Path
 from mysql.connector import CMySQLConnection, MySQLConnection, connect
 
 # FIXME 2026-04-02: For some reason MySQLConnectionAbstract cannot be used
-type DbConnection = Union[PgConnection, MySQLConnection, CMySQLConnection]
+type DbConnection = Union[
+    PgConnection, MySQLConnection, CMySQLConnection, sqlite3.Connection
+]
 
 
 def read_env_config() -> Dict[str, str]:
@@ -43,6 +46,9 @@ def create_connection() -> DbConnection:
             user=env_config["user"],
             password=env_config["password"],
         )
+    elif env_config["protocol"] == "sqlite":
+        # The name is the path of the database file
+        return sqlite3.connect(env_config["database"])
     else:
         raise RuntimeError(f"Unsupported DB protocol {env_config['protocol']}")
 
@@ -72,8 +78,10 @@ def execute_query(
             return query_result
         finally:
             cursor.close()
-    elif isinstance(connection, MySQLConnection) or isinstance(
-        connection, CMySQLConnection
+    elif (
+        isinstance(connection, MySQLConnection)
+        or isinstance(connection, CMySQLConnection)
+        or isinstance(connection, sqlite3.Connection)
     ):
         try:
             cursor = connection.cursor()